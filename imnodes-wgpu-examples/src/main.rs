@@ -77,6 +77,11 @@ fn main() {
     let font_size = (13.0 * hidpi_factor) as f32;
     imgui.io_mut().font_global_scale = (1.0 / hidpi_factor) as f32;
 
+    // imgui scales the whole frame by its framebuffer scale, the imnodes style only needs
+    // the rest of the scale factor.
+    imnodes_ui
+        .set_style_scale_factor(hidpi_factor as f32 / imgui.io().display_framebuffer_scale[0]);
+
     imgui
         .fonts()
         .add_font(&[imgui::FontSource::DefaultFontData {
@@ -118,6 +123,10 @@ fn main() {
                     match window_event {
                         WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
                             hidpi_factor = scale_factor;
+                            // `handle_event` already updated the framebuffer scale.
+                            imnodes_ui.set_style_scale_factor(
+                                hidpi_factor as f32 / imgui.io().display_framebuffer_scale[0],
+                            );
                         }
                         WindowEvent::Resized(size) => {
                             if size.width > 0 && size.height > 0 {
@@ -1,53 +1,57 @@
+use imnodes::graph::{Graph, GraphEvent, GraphNode, LinkPolicy};
 use imnodes::{
-    AttributeFlags, AttributeId, Context, EditorContext, IdentifierGenerator, InputPinId, LinkId,
-    NodeId, OutputPinId, PinShape, Style, editor,
+    AttributeFlags, AttributeId, Context, EditorContext, NodeScope, PinShape, Style, editor,
 };
 
 pub struct MultiEditState {
     pub editor_context: EditorContext,
-    id_gen: IdentifierGenerator,
-    nodes: Vec<Node>,
-    links: Vec<Link>,
-    style: Style,
+    graph: Graph<Node>,
+    /// Why the last link the user created was rejected.
+    rejection: Option<String>,
 }
 
-struct Link {
-    id: LinkId,
-    start: OutputPinId,
-    end: InputPinId,
-}
 struct Node {
-    id: NodeId,
-    input: InputPinId,
-    output: OutputPinId,
     attribute: AttributeId,
     value: f32,
 }
 
 impl MultiEditState {
     pub fn new(context: &Context) -> Self {
-        let editor_context = context.create_editor();
-        let id_gen = editor_context.new_identifier_generator();
+        let mut editor_context = context.create_editor();
+
+        // Only this editor uses the classic theme, `editor` swaps it in while drawing it.
+        let mut style = Style::default();
+        let _ = editor_context.set_style_colors_classic(&mut style);
+        let _ = editor_context.set_own_style(style);
+
+        let mut graph = Graph::new();
+        let _ = graph.set_link_policy(LinkPolicy::SINGLE_INPUT | LinkPolicy::ACYCLIC);
 
         Self {
-            id_gen,
             editor_context,
-            nodes: vec![],
-            links: vec![],
-            style: Style::default(),
+            graph,
+            rejection: None,
         }
     }
 }
 
+/// Adds a node with one input and one output to the graph.
+fn add_node(graph: &mut Graph<Node>) -> imnodes::NodeId {
+    let attribute = graph.identifier_generator().next_attribute();
+    let id = graph.add_node(Node {
+        attribute,
+        value: 0.0,
+    });
+    let _ = graph.add_input(id);
+    let _ = graph.add_output(id);
+    id
+}
+
 /// https://github.com/Nelarius/imnodes/blob/master/example/multi_editor.cpp
 pub fn show(ui: &imgui::Ui, state: &mut MultiEditState) {
     // Push unique ID for this editor instance using push_id_ptr with a reference
     let id = ui.push_id_ptr(&state.editor_context);
 
-    state
-        .editor_context
-        .set_style_colors_classic(&mut state.style);
-
     let on_snap = state
         .editor_context
         .push_attribute_flag(AttributeFlags::EnableLinkCreationOnSnap);
@@ -57,89 +61,65 @@ pub fn show(ui: &imgui::Ui, state: &mut MultiEditState) {
 
     let MultiEditState {
         editor_context,
-        nodes,
-        links,
-        id_gen,
-        ..
+        graph,
+        rejection,
     } = state;
 
     if ui.button("Add a Node") {
-        nodes.push(Node {
-            id: id_gen.next_node(),
-            input: id_gen.next_input_pin(),
-            output: id_gen.next_output_pin(),
-            value: 0.0,
-            attribute: id_gen.next_attribute(),
-        });
+        let _ = add_node(graph);
     }
 
     ui.same_line();
 
     ui.text("or you can press \"A\" or right click");
 
+    if let Some(rejection) = rejection {
+        ui.text_colored([1.0, 0.4, 0.4, 1.0], &*rejection);
+    }
+
     let outer_scope = editor(editor_context, |mut editor| {
         if editor.is_hovered()
             && (ui.is_key_released(imgui::Key::A) || ui.is_mouse_clicked(imgui::MouseButton::Right))
         {
-            let id = id_gen.next_node();
+            let id = add_node(graph);
             let [x, y] = ui.io().mouse_pos;
-            let _ = id.set_position(x, y, imnodes::CoordinateSystem::ScreenSpace);
-            nodes.push(Node {
-                id,
-                input: id_gen.next_input_pin(),
-                output: id_gen.next_output_pin(),
-                value: 0.0,
-                attribute: id_gen.next_attribute(),
-            });
+            let _ = editor
+                .node(id)
+                .set_position(x, y, imnodes::CoordinateSystem::ScreenSpace);
         }
 
-        // Iterate using indices to allow mutable borrow inside slider closure
-        for i in 0..nodes.len() {
-            let node_id = nodes[i].id;
-            let input_pin = nodes[i].input;
-            let output_pin = nodes[i].output;
-            let attr_id = nodes[i].attribute;
-
-            editor.add_node(node_id, |mut node_scope| {
+        graph.render(
+            &mut editor,
+            &mut |node: &mut GraphNode<Node>, mut node_scope: NodeScope<'_>| {
                 node_scope.add_titlebar(|| {
                     ui.text("node");
                 });
 
-                node_scope.add_input(input_pin, PinShape::QuadFilled, || {
+                node_scope.add_input(node.inputs()[0], PinShape::QuadFilled, || {
                     ui.text("input");
                 });
 
-                node_scope.add_static_attribute(attr_id, || {
+                let Node { attribute, value } = &mut node.data;
+                node_scope.add_static_attribute(*attribute, || {
                     ui.set_next_item_width(130.0);
-                    // Get mutable access only for the slider build call
-                    if let Some(node_mut) = nodes.get_mut(i) {
-                        ui.slider_config("value", 0.0, 10.0)
-                            .display_format(format!("{:.2}", node_mut.value))
-                            .build(&mut node_mut.value);
-                    }
+                    ui.slider_config("value", 0.0, 10.0)
+                        .display_format(format!("{value:.2}"))
+                        .build(value);
                 });
 
-                node_scope.add_output(output_pin, PinShape::CircleFilled, || {
+                node_scope.add_output(node.outputs()[0], PinShape::CircleFilled, || {
                     ui.text("output");
                 });
-            });
-        }
-
-        for Link { id, start, end } in links {
-            editor.add_link(*id, *end, *start);
-        }
+            },
+        );
     });
 
-    if let Some(link) = outer_scope.links_created() {
-        state.links.push(Link {
-            id: state.id_gen.next_link(),
-            start: link.start_pin,
-            end: link.end_pin,
-        });
-    }
-
-    if let Some(link_id) = outer_scope.get_destroyed_link() {
-        state.links.retain(|link| link.id != link_id);
+    for event in graph.apply_events(&outer_scope) {
+        match event {
+            GraphEvent::LinkAdded(_) => *rejection = None,
+            GraphEvent::LinkRejected(reason) => *rejection = Some(reason.to_string()),
+            GraphEvent::LinkRemoved(_) => {}
+        }
     }
 
     on_snap.pop();
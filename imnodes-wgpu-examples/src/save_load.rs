@@ -1,6 +1,6 @@
 use imnodes::{
-    Context, CoordinateSystem, EditorContext, IdentifierGenerator, InputPinId, LinkId, NodeId,
-    OutputPinId, PinShape, editor,
+    Context, ContextMenuTarget, CoordinateSystem, EditorContext, IdentifierGenerator, InputPinId,
+    LinkId, NodeId, OutputPinId, PinShape, SelectionChange, editor,
 };
 
 // WARNING! this file is not finished yet/ save load does not work yet
@@ -27,8 +27,8 @@ pub struct SaveLoadState {
     links: Vec<AppLink>,
     saved_state_string: Option<String>,
     status: String,
-    last_selected_nodes: Vec<NodeId>,
-    last_selected_links: Vec<LinkId>,
+    // The selection at the end of the last frame, as reported by `selection_changed`.
+    selection: SelectionChange,
     add_node_at: Option<[f32; 2]>,
 }
 
@@ -43,13 +43,12 @@ impl SaveLoadState {
             links: vec![],
             saved_state_string: None,
             status: "Ready".to_string(),
-            last_selected_nodes: vec![],
-            last_selected_links: vec![],
+            selection: SelectionChange::default(),
             add_node_at: None,
         }
     }
 
-    // Helper function to add a node
+    // Helper function to add a node, `position` is in grid space
     fn add_node(&mut self, position: Option<[f32; 2]>) {
         let node_id = self.id_gen.next_node();
         let new_node = AppNode {
@@ -59,8 +58,9 @@ impl SaveLoadState {
         };
 
         // Set position *before* pushing the node, so it's placed correctly on the first frame
+        let node = self.editor_context.node(node_id);
         if let Some(pos) = position {
-            let _ = node_id.set_position(pos[0], pos[1], CoordinateSystem::ScreenSpace);
+            let _ = node.set_position(pos[0], pos[1], CoordinateSystem::GridSpace);
         } else {
             // Place the new node near the center of the screen, adjusted by panning.
             let pan = self.editor_context.get_panning();
@@ -68,12 +68,28 @@ impl SaveLoadState {
             // Place relative to pan for now. A better approach might involve passing window size.
             let node_x = pan.x + 100.0; // Offset from top-left visible corner
             let node_y = pan.y + 100.0;
-            let _ = node_id.set_position(node_x, node_y, CoordinateSystem::GridSpace);
+            let _ = node.set_position(node_x, node_y, CoordinateSystem::GridSpace);
         }
 
         self.nodes.push(new_node);
         self.status = format!("Added Node {node_id:?}");
     }
+
+    fn remove_node(&mut self, node_id: NodeId) {
+        if let Some(index) = self.nodes.iter().position(|node| node.id == node_id) {
+            let node = self.nodes.remove(index);
+            self.links
+                .retain(|link| node.input != link.end_pin && node.output != link.start_pin);
+            self.status = format!("Removed Node {node_id:?}");
+        }
+    }
+}
+
+/// What the user picked in the context menu of the editor.
+enum MenuAction {
+    AddNode([f32; 2]),
+    RemoveNode(NodeId),
+    RemoveLink(LinkId),
 }
 
 pub fn show(ui: &imgui::Ui, state: &mut SaveLoadState) {
@@ -85,11 +101,10 @@ pub fn show(ui: &imgui::Ui, state: &mut SaveLoadState) {
     }
     ui.same_line();
     if ui.button("Remove Selected Nodes") {
-        if !state.last_selected_nodes.is_empty() {
+        if !state.selection.nodes.is_empty() {
             let mut removed_count = 0;
-            let nodes_to_remove = state.last_selected_nodes.clone();
             state.nodes.retain(|node| {
-                if nodes_to_remove.contains(&node.id) {
+                if state.selection.nodes.contains(&node.id) {
                     state
                         .links
                         .retain(|link| node.input != link.end_pin && node.output != link.start_pin);
@@ -101,7 +116,6 @@ pub fn show(ui: &imgui::Ui, state: &mut SaveLoadState) {
             });
             state.status = format!("Removed {removed_count} node(s)");
             state.editor_context.clear_node_selection();
-            state.last_selected_nodes.clear();
         } else {
             state.status = "No nodes selected to remove".to_string();
         }
@@ -114,12 +128,12 @@ pub fn show(ui: &imgui::Ui, state: &mut SaveLoadState) {
     ui.text("Save/Load:");
     if ui.button("Save to String") {
         match state.editor_context.save_current_editor_state_to_string() {
-            Some(saved_str) => {
+            Ok(saved_str) => {
                 state.saved_state_string = Some(saved_str);
                 state.status = "Saved state to internal string".to_string();
             }
-            None => {
-                state.status = "Failed to save state to string".to_string();
+            Err(e) => {
+                state.status = format!("Failed to save state to string: {e}");
             }
         }
     }
@@ -127,13 +141,16 @@ pub fn show(ui: &imgui::Ui, state: &mut SaveLoadState) {
     if ui.button("Load from String") {
         if let Some(saved_str) = &state.saved_state_string {
             // Load the imnodes internal state
-            state
+            match state
                 .editor_context
-                .load_current_editor_state_from_string(saved_str);
-            state.last_selected_nodes.clear();
-            state.last_selected_links.clear();
-            state.status =
-                "Loaded imnodes state from string. App state assumed to match.".to_string();
+                .load_current_editor_state_from_string(saved_str)
+            {
+                Ok(()) => {
+                    state.status =
+                        "Loaded imnodes state from string. App state assumed to match.".to_string();
+                }
+                Err(e) => state.status = format!("Failed to load state from string: {e}"),
+            }
         } else {
             state.status = "No saved string state to load".to_string();
         }
@@ -155,8 +172,6 @@ pub fn show(ui: &imgui::Ui, state: &mut SaveLoadState) {
     //         .load_current_editor_state_from_file("save_load_state.ini")
     //     {
     //         Ok(_) => {
-    //             state.last_selected_nodes.clear();
-    //             state.last_selected_links.clear();
     //             state.status =
     //                 "Loaded imnodes state from file. App state assumed to match.".to_string();
     //         }
@@ -179,17 +194,15 @@ pub fn show(ui: &imgui::Ui, state: &mut SaveLoadState) {
         state.status = "Panning reset".to_string();
     }
 
-    ui.text(format!(
-        "Selected Nodes: {}",
-        state.last_selected_nodes.len()
-    ));
-    if state.last_selected_nodes.len() == 1 {
-        let node_id = state.last_selected_nodes[0];
+    ui.text(format!("Selected Nodes: {}", state.selection.nodes.len()));
+    if state.selection.nodes.len() == 1 {
+        let node_id = state.selection.nodes[0];
         // Check if the node still exists in our app state before getting position
         if state.nodes.iter().any(|n| n.id == node_id) {
-            let screen_pos = node_id.get_position(CoordinateSystem::ScreenSpace);
-            let editor_pos = node_id.get_position(CoordinateSystem::EditorSpace);
-            let grid_pos = node_id.get_position(CoordinateSystem::GridSpace);
+            let node = state.editor_context.node(node_id);
+            let screen_pos = node.get_position(CoordinateSystem::ScreenSpace);
+            let editor_pos = node.get_position(CoordinateSystem::EditorSpace);
+            let grid_pos = node.get_position(CoordinateSystem::GridSpace);
             ui.text(format!("  Node {node_id:?} Pos:"));
             ui.text(format!(
                 "    Screen: {:.1}, {:.1}",
@@ -203,33 +216,28 @@ pub fn show(ui: &imgui::Ui, state: &mut SaveLoadState) {
 
             ui.same_line();
             if ui.button("Deselect Node") {
-                let _ = node_id.deselect();
+                let _ = node.deselect();
             }
             ui.same_line();
             if ui.button("Snap to Grid") {
-                let _ = node_id.snap_to_grid();
+                let _ = node.snap_to_grid();
             }
         } else {
             // Node was likely removed after selection but before redraw
             ui.text(format!("  Node {node_id:?} (removed)"));
         }
-    } else if state.last_selected_nodes.len() > 1 {
+    } else if state.selection.nodes.len() > 1 {
         ui.same_line();
         if ui.button("Clear Node Selection") {
             state.editor_context.clear_node_selection();
-            state.last_selected_nodes.clear();
         }
     }
 
-    ui.text(format!(
-        "Selected Links: {}",
-        state.last_selected_links.len()
-    ));
-    if !state.last_selected_links.is_empty() {
+    ui.text(format!("Selected Links: {}", state.selection.links.len()));
+    if !state.selection.links.is_empty() {
         ui.same_line();
         if ui.button("Clear Link Selection") {
             state.editor_context.clear_link_selection();
-            state.last_selected_links.clear();
         }
     }
 
@@ -237,17 +245,36 @@ pub fn show(ui: &imgui::Ui, state: &mut SaveLoadState) {
     ui.text(format!("Status: {}", state.status));
     ui.separator();
 
-    // Store context menu click position *outside* the editor closure
-    let mut context_menu_pos = None;
+    // Store the requests *outside* the editor closure, they are handled after it
+    let mut add_node_key_pos = None;
+    let mut menu_action = None;
     let outer_scope = editor(&mut state.editor_context, |mut editor_scope| {
-        // Detect context menu click *inside* the editor scope
-        if editor_scope.is_hovered()
-            && (ui.is_key_released(imgui::Key::A) || ui.is_mouse_clicked(imgui::MouseButton::Right))
-        {
-            // Store the position where the node should be added
-            context_menu_pos = Some(ui.io().mouse_pos);
+        if editor_scope.is_hovered() && ui.is_key_released(imgui::Key::A) {
+            // Store the screen position where the node should be added
+            add_node_key_pos = Some(ui.io().mouse_pos);
         }
 
+        editor_scope.context_menu(ui, "context_menu", |target, grid_pos| match target {
+            ContextMenuTarget::Canvas => {
+                if ui.menu_item("Add Node") {
+                    menu_action = Some(MenuAction::AddNode(grid_pos));
+                }
+            }
+            ContextMenuTarget::Node(node_id) => {
+                if ui.menu_item("Remove Node") {
+                    menu_action = Some(MenuAction::RemoveNode(node_id));
+                }
+            }
+            ContextMenuTarget::Link(link_id) => {
+                if ui.menu_item("Remove Link") {
+                    menu_action = Some(MenuAction::RemoveLink(link_id));
+                }
+            }
+            ContextMenuTarget::Pin(pin_id) => {
+                ui.text(format!("Pin {pin_id:?}"));
+            }
+        });
+
         // Iterate using indices to allow mutable borrow inside slider closure
         for i in 0..state.nodes.len() {
             // Need to get these before the mutable borrow below
@@ -287,10 +314,19 @@ pub fn show(ui: &imgui::Ui, state: &mut SaveLoadState) {
         }
     });
 
-    // Handle node addition request *after* the editor scope ends
-    if let Some(pos) = context_menu_pos {
+    // Handle the requests *after* the editor scope ends
+    match menu_action {
+        Some(MenuAction::AddNode(pos)) => state.add_node(Some(pos)),
+        Some(MenuAction::RemoveNode(node_id)) => state.remove_node(node_id),
+        Some(MenuAction::RemoveLink(link_id)) => {
+            state.links.retain(|link| link.id != link_id);
+            state.status = format!("Removed Link {link_id:?}");
+        }
+        None => {}
+    }
+    if let Some(pos) = add_node_key_pos {
+        let pos = state.editor_context.screen_to_grid_space(pos);
         state.add_node(Some(pos));
-        // state.add_node_at = None; // Not needed anymore
     } else if let Some(pos) = state.add_node_at {
         if pos[0] == -1.0 {
             // Check for sentinel value from button click
@@ -299,9 +335,10 @@ pub fn show(ui: &imgui::Ui, state: &mut SaveLoadState) {
         state.add_node_at = None; // Reset request
     }
 
-    // Update stored selections for the *next* frame
-    state.last_selected_nodes = outer_scope.selected_nodes();
-    state.last_selected_links = outer_scope.selected_links();
+    // The editor reports the selection whenever it changes, e.g. after clearing it above
+    if let Some(change) = outer_scope.selection_changed() {
+        state.selection.clone_from(change);
+    }
 
     if let Some(new_link) = outer_scope.links_created() {
         let new_app_link = AppLink {
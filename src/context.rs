@@ -1,15 +1,49 @@
-use crate::{ImNodesIO, Style, sys};
-use std::ffi::{CStr, CString};
+use crate::{
+    ContextMenuTarget, EditorState, Error, EventTracker, FrameState, ImNodesIO, PendingContextMenu,
+    PendingLinkDrop, PinId, Style, SubmittedPin, sys,
+};
+use std::cell::{Cell, RefCell};
+use std::fs;
+use std::io;
+use std::mem;
 use std::path::Path;
+use std::rc::Rc;
 
 /// An editor context corresponds to a set of nodes in a single workspace
 ///
 /// By default, the library creates an editor context behind the scenes, so using any of the imnodes
 /// functions doesn't require you to explicitly create a context. However, creating explicit contexts
 /// allows for multiple editor instances.
+///
+/// An editor context keeps the [`Context`] it was created from alive, so the two can be
+/// dropped in any order:
+///
+/// ```
+/// # let _imgui = imgui::Context::create();
+/// let context = imnodes::Context::new();
+/// let editor = context.create_editor();
+/// // The global context is only destroyed once `editor` is gone as well.
+/// drop(context);
+/// drop(editor);
+/// ```
 #[derive(Debug)]
 pub struct EditorContext {
     raw: *mut sys::ImNodesEditorContext,
+    pub(crate) events: EventTracker,
+    pub(crate) frame: FrameState,
+    // Screen space position of the canvas in the last frame.
+    pub(crate) canvas_origin: [f32; 2],
+    // The pin, link or node hovered at the end of the last frame.
+    pub(crate) hovered: Option<ContextMenuTarget>,
+    pub(crate) pending_link_drop: Option<PendingLinkDrop>,
+    pub(crate) pending_context_menu: Option<PendingContextMenu>,
+    // The pin the user is dragging a new link from.
+    pub(crate) link_drag: Option<(PinId, SubmittedPin)>,
+    // Replace the shared style and IO settings while this editor is drawn.
+    pub(crate) style: Option<Style>,
+    pub(crate) io: Option<ImNodesIO>,
+    // Dropped after `raw` has been freed in `Drop::drop`.
+    context: Rc<RawContext>,
 }
 
 impl EditorContext {
@@ -24,6 +58,34 @@ impl EditorContext {
         self
     }
 
+    /// Makes this editor and the [`Context`] it was created from current to run `f`.
+    ///
+    /// The previously current contexts are restored afterwards. If another editor of the same
+    /// context is being drawn by [`crate::editor()`], it is made current again as well, so calls
+    /// into this editor cannot end up in the other editor's frame.
+    pub(crate) fn with_current_editor<R>(&self, f: impl FnOnce() -> R) -> R {
+        self.context.with_current(|| {
+            let _ = self.set_as_current_editor();
+            let result = f();
+            let drawn = self.context.drawn_editor.get();
+            if !drawn.is_null() && drawn != self.raw {
+                // Safety: C API call. The drawn editor is alive until it has ended.
+                unsafe { sys::imnodes_EditorContextSet(drawn) };
+            }
+            result
+        })
+    }
+
+    /// Records whether this editor is being drawn, between `BeginNodeEditor` and `EndNodeEditor`.
+    pub(crate) fn set_drawn(&self, drawn: bool) {
+        let editor = if drawn {
+            self.raw
+        } else {
+            std::ptr::null_mut()
+        };
+        self.context.drawn_editor.set(editor);
+    }
+
     /// Creates a new identifier generator associated with this editor context.
     ///
     /// Each editor should ideally use its own generator to avoid ID clashes
@@ -35,10 +97,42 @@ impl EditorContext {
         crate::IdentifierGenerator::new()
     }
 
+    /// Replaces the shared style and IO settings of the current context with the ones owned
+    /// by this editor. Returns the replaced settings for [`EditorContext::restore_settings`].
+    pub(crate) fn apply_own_settings(&self) -> (Option<Style>, Option<ImNodesIO>) {
+        let style = self.style.as_ref().map(|style| {
+            let mut style = style.clone();
+            let _ = style.scale_all_sizes(self.context.style_scale.get());
+            // Safety: Accesses the style of the current context. `Style` is `repr(transparent)`.
+            mem::replace(
+                unsafe { &mut *sys::imnodes_GetStyle().cast::<Style>() },
+                style,
+            )
+        });
+        // Safety: Accesses the IO settings of the current context.
+        let io = self
+            .io
+            .map(|io| mem::replace(unsafe { &mut *sys::imnodes_GetIO() }, io));
+        (style, io)
+    }
+
+    /// Restores the shared settings replaced by [`EditorContext::apply_own_settings`].
+    pub(crate) fn restore_settings(&self, (style, io): (Option<Style>, Option<ImNodesIO>)) {
+        if let Some(style) = style {
+            // Safety: Accesses the style of the current context.
+            unsafe { *sys::imnodes_GetStyle().cast::<Style>() = style };
+        }
+        if let Some(io) = io {
+            // Safety: Accesses the IO settings of the current context.
+            unsafe { *sys::imnodes_GetIO() = io };
+        }
+    }
+
     /// Returns a mutable reference to the global style variables shared across all editor contexts.
     ///
     /// Use this to modify the *currently active* style. To get a copy of the default style,
-    /// use [`crate::Style::default()`].
+    /// use [`crate::Style::default()`]. To give a single editor its own style, use
+    /// [`EditorContext::set_own_style`].
     #[doc(alias = "GetStyle")]
     pub fn get_style(&mut self) -> &mut Style {
         // Safety: This accesses the global style object managed by imnodes.
@@ -46,9 +140,14 @@ impl EditorContext {
         unsafe { &mut *(sys::imnodes_GetStyle() as *mut Style) }
     }
 
+    pub(crate) fn raw_io(&self) -> (*mut ImNodesIO, *const imgui::sys::ImGuiIO) {
+        self.context.io()
+    }
+
     /// Returns a mutable reference to the global IO settings shared across all editor contexts.
     ///
     /// Use this to configure input behaviors like modifier keys.
+    /// [`EditorContext::io`] sets the modifier keys without raw pointers.
     #[doc(alias = "GetIO")]
     pub fn get_io(&mut self) -> &mut ImNodesIO {
         // Safety: This accesses the global IO object managed by imnodes.
@@ -56,11 +155,8 @@ impl EditorContext {
     }
 
     /// Saves the state of the currently active editor context to a string.
-    ///
-    /// Returns `None` if saving fails or the resulting string is invalid UTF-8.
     #[doc(alias = "SaveCurrentEditorStateToIniString")]
-    #[must_use]
-    pub fn save_current_editor_state_to_string(&self) -> Option<String> {
+    pub fn save_current_editor_state_to_string(&self) -> Result<String, Error> {
         // Ensure this context is set before saving 'current' state
         let _ = self.set_as_current_editor();
         let mut data_size: usize = 0;
@@ -68,65 +164,52 @@ impl EditorContext {
         // The returned pointer points to memory managed by imnodes.
         let char_ptr =
             unsafe { sys::imnodes_SaveCurrentEditorStateToIniString(&mut data_size as *mut _) };
-        if char_ptr.is_null() || data_size == 0 {
-            return None;
-        }
-        // Safety: We assume the pointer is valid for `data_size` bytes and contains UTF-8 data.
-        // We create a CStr slice from it. Using CStr::from_bytes_until_nul as imnodes might not guarantee null termination exactly at data_size.
-        // If the data is not null terminated *within* data_size, this could read out of bounds, but imnodes *should* null terminate.
-        // A potentially safer approach would involve checking for null termination explicitly if needed.
-        unsafe {
-            CStr::from_bytes_with_nul_unchecked(std::slice::from_raw_parts(
-                char_ptr.cast(),
-                data_size,
-            ))
-        }
-        .to_str()
-        .ok()
-        .map(String::from)
+        // Safety: imnodes returns its text buffer, which is valid until the next save.
+        unsafe { ini_string(char_ptr, data_size) }
     }
 
     /// Saves the state of *this specific* editor context to a string.
-    ///
-    /// Returns `None` if saving fails or the resulting string is invalid UTF-8.
     #[doc(alias = "SaveEditorStateToIniString")]
-    #[must_use]
-    pub fn save_editor_state_to_string(&self) -> Option<String> {
+    pub fn save_editor_state_to_string(&self) -> Result<String, Error> {
         let mut data_size: usize = 0;
         // Safety: C API call. `data_size` is written to by the function.
         // The returned pointer points to memory managed by imnodes.
         let char_ptr =
             unsafe { sys::imnodes_SaveEditorStateToIniString(self.raw, &mut data_size as *mut _) };
-        if char_ptr.is_null() || data_size == 0 {
-            return None;
-        }
         // Safety: See above.
-        unsafe {
-            CStr::from_bytes_with_nul_unchecked(std::slice::from_raw_parts(
-                char_ptr.cast(),
-                data_size,
-            ))
-        }
-        .to_str()
-        .ok()
-        .map(String::from)
+        unsafe { ini_string(char_ptr, data_size) }
     }
 
     /// Loads state into the currently active editor context from a string.
+    ///
+    /// Like imnodes, comments, unknown sections and unknown keys are ignored. Returns
+    /// [`Error::Malformed`] without loading anything if a node ID or value is invalid.
     #[doc(alias = "LoadCurrentEditorStateFromIniString")]
-    pub fn load_current_editor_state_from_string(&self, data: &str) {
+    pub fn load_current_editor_state_from_string(&self, data: &str) -> Result<(), Error> {
+        let _ = data.parse::<EditorState>()?;
         // Ensure this context is set before loading 'current' state
         let _ = self.set_as_current_editor();
-        // Safety: C API call. Assumes `data` points to valid memory for its length.
+        // Safety: C API call. `data` points to valid memory for its length.
         unsafe {
             sys::imnodes_LoadCurrentEditorStateFromIniString(data.as_ptr().cast(), data.len())
         }
+        Ok(())
     }
 
     /// Loads state into *this specific* editor context from a string.
+    ///
+    /// Like imnodes, comments, unknown sections and unknown keys are ignored. Returns
+    /// [`Error::Malformed`] without loading anything if a node ID or value is invalid.
     #[doc(alias = "LoadEditorStateFromIniString")]
-    pub fn load_editor_state_from_string(&self, data: &str) {
-        // Safety: C API call. Assumes `data` points to valid memory for its length.
+    pub fn load_editor_state_from_string(&self, data: &str) -> Result<(), Error> {
+        let _ = data.parse::<EditorState>()?;
+        self.load_valid_editor_state(data);
+        Ok(())
+    }
+
+    /// Loads `data` into this editor without checking it first.
+    pub(crate) fn load_valid_editor_state(&self, data: &str) {
+        // Safety: C API call. `data` points to valid memory for its length.
         unsafe {
             sys::imnodes_LoadEditorStateFromIniString(self.raw, data.as_ptr().cast(), data.len())
         }
@@ -137,24 +220,17 @@ impl EditorContext {
     pub fn save_current_editor_state_to_file<P: AsRef<Path>>(
         &self,
         file_path: P,
-    ) -> std::io::Result<()> {
-        // Ensure this context is set before saving 'current' state
-        let _ = self.set_as_current_editor();
-        let path_str = file_path.as_ref().to_string_lossy();
-        // Use CString for null termination guarantee
-        let c_path = CString::new(path_str.as_bytes())?;
-        // Safety: C API call with a valid CString pointer.
-        unsafe { sys::imnodes_SaveCurrentEditorStateToIniFile(c_path.as_ptr()) };
+    ) -> Result<(), Error> {
+        let data = self.save_current_editor_state_to_string()?;
+        fs::write(file_path, data)?;
         Ok(())
     }
 
     /// Saves the state of *this specific* editor context to an INI file.
     #[doc(alias = "SaveEditorStateToIniFile")]
-    pub fn save_editor_state_to_file<P: AsRef<Path>>(&self, file_path: P) -> std::io::Result<()> {
-        let path_str = file_path.as_ref().to_string_lossy();
-        let c_path = CString::new(path_str.as_bytes())?;
-        // Safety: C API call with a valid CString pointer.
-        unsafe { sys::imnodes_SaveEditorStateToIniFile(self.raw, c_path.as_ptr()) };
+    pub fn save_editor_state_to_file<P: AsRef<Path>>(&self, file_path: P) -> Result<(), Error> {
+        let data = self.save_editor_state_to_string()?;
+        fs::write(file_path, data)?;
         Ok(())
     }
 
@@ -163,48 +239,134 @@ impl EditorContext {
     pub fn load_current_editor_state_from_file<P: AsRef<Path>>(
         &self,
         file_path: P,
-    ) -> std::io::Result<()> {
-        // Ensure this context is set before loading 'current' state
-        let _ = self.set_as_current_editor();
-        let path_str = file_path.as_ref().to_string_lossy();
-        let c_path = CString::new(path_str.as_bytes())?;
-        // Safety: C API call with a valid CString pointer.
-        unsafe { sys::imnodes_LoadCurrentEditorStateFromIniFile(c_path.as_ptr()) };
-        Ok(())
+    ) -> Result<(), Error> {
+        let data = read_ini_file(file_path.as_ref())?;
+        self.load_current_editor_state_from_string(&data)
     }
 
     /// Loads state into *this specific* editor context from an INI file.
     #[doc(alias = "LoadEditorStateFromIniFile")]
-    pub fn load_editor_state_from_file<P: AsRef<Path>>(&self, file_path: P) -> std::io::Result<()> {
-        let path_str = file_path.as_ref().to_string_lossy();
-        let c_path = CString::new(path_str.as_bytes())?;
-        // Safety: C API call with a valid CString pointer.
-        unsafe { sys::imnodes_LoadEditorStateFromIniFile(self.raw, c_path.as_ptr()) };
-        Ok(())
+    pub fn load_editor_state_from_file<P: AsRef<Path>>(&self, file_path: P) -> Result<(), Error> {
+        let data = read_ini_file(file_path.as_ref())?;
+        self.load_editor_state_from_string(&data)
     }
 }
 
+/// Copies the string imnodes saved an editor state to.
+///
+/// # Safety
+///
+/// `ptr` must be null or valid for reads of `size` bytes.
+unsafe fn ini_string(ptr: *const std::os::raw::c_char, size: usize) -> Result<String, Error> {
+    if ptr.is_null() {
+        return Err(Error::NotSaved);
+    }
+    // Safety: Guaranteed by the caller. `size` excludes the terminating NUL, so only the
+    // returned text is read.
+    let bytes = unsafe { std::slice::from_raw_parts(ptr.cast::<u8>(), size) };
+    // Stop at an embedded NUL, in case the size includes the terminator.
+    let text = bytes.split(|&byte| byte == 0).next().unwrap_or_default();
+    Ok(std::str::from_utf8(text)?.to_owned())
+}
+
+/// Reads a saved editor state from `path`.
+fn read_ini_file(path: &Path) -> Result<String, Error> {
+    let data = fs::read(path).map_err(|error| match error.kind() {
+        io::ErrorKind::NotFound => Error::NotFound(path.to_owned()),
+        _ => Error::Io(error),
+    })?;
+    String::from_utf8(data).map_err(|error| Error::InvalidUtf8(error.utf8_error()))
+}
+
 impl Drop for EditorContext {
     /// Frees the editor context if it was created explicitly via `Context::create_editor`.
     #[doc(alias = "EditorContextFree")]
     fn drop(&mut self) {
+        // An editor dropped while it is drawn, e.g. during a panic, must not be made current.
+        if self.context.drawn_editor.get() == self.raw {
+            self.set_drawn(false);
+        }
         // Safety: Frees the context created by `imnodes_EditorContextCreate`.
-        // Only called if `owned` is true.
+        // The global context is still alive as `context` is dropped afterwards.
         unsafe {
             sys::imnodes_EditorContextFree(self.raw);
         }
     }
 }
 
+/// Owns the raw global imnodes context.
+///
+/// Shared between a [`Context`] and all [`EditorContext`]s created from it, so it is only
+/// destroyed once the last of them has been dropped.
+#[derive(Debug)]
+struct RawContext {
+    raw: *mut sys::ImNodesContext,
+    // The imgui context that was current when this context was created.
+    imgui: *mut imgui::sys::ImGuiContext,
+    // Factor all style sizes are scaled by, see `Context::set_style_scale_factor`.
+    style_scale: Cell<f32>,
+    // The shared style before scaling, the scaled style is derived from it. Taken when the
+    // style is scaled for the first time.
+    unscaled_style: RefCell<Option<Style>>,
+    // The editor between `BeginNodeEditor` and `EndNodeEditor`, or null.
+    drawn_editor: Cell<*mut sys::ImNodesEditorContext>,
+}
+
+impl RawContext {
+    /// Returns the IO settings of this context and the IO of the imgui context it is bound to.
+    ///
+    /// Both live as long as their contexts, independent of which contexts are current.
+    fn io(&self) -> (*mut ImNodesIO, *const imgui::sys::ImGuiIO) {
+        // Safety: C API calls. Both contexts are current while reading their IO.
+        self.with_current(|| unsafe { (sys::imnodes_GetIO(), imgui::sys::igGetIO().cast_const()) })
+    }
+
+    /// Returns the shared style of this context, which lives as long as the context.
+    fn style(&self) -> *mut Style {
+        // Safety: C API call. This context is current while reading its style.
+        self.with_current(|| unsafe { sys::imnodes_GetStyle().cast::<Style>() })
+    }
+
+    /// Temporarily makes both contexts current to run `f`, then restores the previous ones.
+    fn with_current<R>(&self, f: impl FnOnce() -> R) -> R {
+        // Safety: C API calls. Both contexts are alive as long as `self` is.
+        unsafe {
+            let previous = sys::imnodes_GetCurrentContext();
+            let previous_imgui = imgui::sys::igGetCurrentContext();
+            sys::imnodes_SetCurrentContext(self.raw);
+            imgui::sys::igSetCurrentContext(self.imgui);
+            let result = f();
+            imgui::sys::igSetCurrentContext(previous_imgui);
+            sys::imnodes_SetCurrentContext(previous);
+            result
+        }
+    }
+}
+
+impl Drop for RawContext {
+    /// Destroys the global imnodes context.
+    fn drop(&mut self) {
+        // Safety: Destroys the global context created by `imnodes_CreateContext`.
+        // No editor context created from it is alive anymore.
+        unsafe { sys::imnodes_DestroyContext(self.raw) }
+    }
+}
+
 /// Represents the global imnodes context.
 ///
 /// This should be created once at the start of the application, typically alongside
-/// the `imgui::Context`.
+/// the `imgui::Context`. It is bound to the imgui context that is current at creation time.
+///
+/// When using several imgui contexts, create one `Context` per imgui context and
+/// call [`Context::make_current`] before drawing any node editor into it.
+///
+/// The underlying imnodes context is destroyed when the `Context` and every
+/// [`EditorContext`] created from it have been dropped.
 #[doc(alias = "imnodes_CreateContext")]
 #[derive(Debug)]
 pub struct Context {
     // Renamed from ImnodesContext to avoid confusion
-    context: *mut sys::ImNodesContext,
+    context: Rc<RawContext>,
 }
 
 impl Default for Context {
@@ -215,36 +377,379 @@ impl Default for Context {
 }
 
 impl Context {
-    /// Creates a new global imnodes context.
+    /// Creates a new global imnodes context bound to the currently active imgui context.
+    ///
+    /// If no imnodes context is current yet, the new one becomes the current context.
+    ///
+    /// Panics if there is no active imgui context.
     #[must_use]
     pub fn new() -> Self {
+        // Safety: C API call. Only reads the thread-local imgui context pointer.
+        let imgui_context = unsafe { imgui::sys::igGetCurrentContext() };
+        assert!(
+            !imgui_context.is_null(),
+            "An imgui context must be active when creating an imnodes `Context`"
+        );
         // Safety: Creates the global imnodes context. Should be called once.
         let context = unsafe { sys::imnodes_CreateContext() };
-        // Safety: Ensure the associated ImGui context is also set for imnodes.
-        // We need to cast the pointer type because imgui-sys and imnodes-sys
-        // define their own (but compatible) ImGuiContext types.
-        unsafe {
-            sys::imnodes_SetImGuiContext(imgui::sys::igGetCurrentContext() as *mut sys::ImGuiContext)
-        };
-        Self { context }
+        // Ensure the associated ImGui context is also set for imnodes.
+        let _ = bind_imgui(imgui_context);
+        Self {
+            context: Rc::new(RawContext {
+                raw: context,
+                imgui: imgui_context,
+                style_scale: Cell::new(1.0),
+                unscaled_style: RefCell::new(None),
+                drawn_editor: Cell::new(std::ptr::null_mut()),
+            }),
+        }
+    }
+
+    /// Checks if this is the current imnodes context.
+    #[doc(alias = "GetCurrentContext")]
+    #[must_use]
+    pub fn is_current(&self) -> bool {
+        // Safety: C API call. Only reads the current context pointer.
+        unsafe { sys::imnodes_GetCurrentContext() == self.context.raw }
+    }
+
+    /// Makes this the current imnodes context until the returned guard is dropped.
+    ///
+    /// Dropping the guard restores the imnodes and imgui contexts that were current before, as
+    /// well as the imgui context imnodes was bound to.
+    ///
+    /// Panics if the imgui context this context was created against is not the active one,
+    /// e.g. because another `imgui::Context` was activated in the meantime.
+    ///
+    /// ```no_run
+    /// # let tool_imgui = imgui::Context::create();
+    /// let tool_nodes = imnodes::Context::new();
+    /// {
+    ///     let _current = tool_nodes.make_current();
+    ///     // draw the node editors of the tool window
+    /// }
+    /// ```
+    #[doc(alias = "SetCurrentContext")]
+    #[must_use = "The context is only current while the returned guard is alive"]
+    pub fn make_current(&self) -> CurrentContextGuard<'_> {
+        // Safety: C API calls. Only read the thread-local context pointers.
+        let previous = unsafe { sys::imnodes_GetCurrentContext() };
+        let previous_imgui = unsafe { imgui::sys::igGetCurrentContext() };
+        assert!(
+            previous_imgui == self.context.imgui,
+            "The imgui context this imnodes `Context` was created against is not the active one"
+        );
+        // Safety: C API call. The context is alive as long as `self` is.
+        unsafe { sys::imnodes_SetCurrentContext(self.context.raw) };
+        CurrentContextGuard {
+            previous,
+            previous_imgui,
+            previous_bound_imgui: bind_imgui(self.context.imgui),
+            context: self,
+        }
+    }
+
+    pub(crate) fn raw_io(&self) -> (*mut ImNodesIO, *const imgui::sys::ImGuiIO) {
+        self.context.io()
+    }
+
+    /// Scales all sizes of the shared style by `factor`, e.g. the scale factor of the monitor.
+    ///
+    /// The first call keeps an unscaled copy of the shared style, and every call derives the
+    /// shared style from that copy, so call this again whenever the platform reports a new scale
+    /// factor. Changes made to [`EditorContext::get_style`] after the first call are replaced
+    /// by the next one, use [`Context::set_unscaled_style`] to change the style instead. Styles
+    /// owned by editors, see [`EditorContext::set_own_style`], are scaled by the same factor
+    /// while drawing them.
+    ///
+    /// Only the part of the scale factor imgui does not already apply to the whole frame via
+    /// its framebuffer scale needs to be set here.
+    ///
+    /// Panics if `factor` is not a positive number.
+    ///
+    /// ```no_run
+    /// # let context: imnodes::Context = unimplemented!();
+    /// # let imgui: imgui::Context = unimplemented!();
+    /// # let scale_factor = 2.0;
+    /// // e.g. on `winit::event::WindowEvent::ScaleFactorChanged`
+    /// context.set_style_scale_factor(scale_factor / imgui.io().display_framebuffer_scale[0]);
+    /// ```
+    #[doc(alias = "ScaleAllSizes")]
+    pub fn set_style_scale_factor(&self, factor: f32) {
+        assert!(
+            factor.is_finite() && factor > 0.0,
+            "The style scale factor must be a positive number, got {factor}"
+        );
+        self.context.style_scale.set(factor);
+        // Safety: The style lives as long as the context.
+        let style = unsafe { &mut *self.context.style() };
+        let mut unscaled = self.context.unscaled_style.borrow_mut();
+        // Not scaled yet, as the factor starts at 1 and `set_unscaled_style` keeps its style.
+        let mut scaled = unscaled.get_or_insert_with(|| style.clone()).clone();
+        let _ = scaled.scale_all_sizes(factor);
+        *style = scaled;
+    }
+
+    /// Returns the factor the style sizes are scaled by, `1.0` by default.
+    #[must_use]
+    pub fn style_scale_factor(&self) -> f32 {
+        self.context.style_scale.get()
+    }
+
+    /// Replaces the shared style by `style` scaled by [`Context::style_scale_factor`].
+    ///
+    /// `style` is kept to derive the shared style from when the scale factor changes. Use this
+    /// to change the style, e.g. to [`crate::Style::default`], once the style is scaled.
+    pub fn set_unscaled_style(&self, style: Style) {
+        let mut scaled = style.clone();
+        let _ = scaled.scale_all_sizes(self.context.style_scale.get());
+        *self.context.unscaled_style.borrow_mut() = Some(style);
+        // Safety: The style lives as long as the context.
+        unsafe { *self.context.style() = scaled };
     }
 
     /// Creates an editor context for managing a single node editor workspace.
     ///
     /// This allows for multiple independent node editor instances.
+    /// The returned editor keeps this context alive until it is dropped.
     #[must_use]
     pub fn create_editor(&self) -> EditorContext {
         EditorContext {
             // Safety: Creates a new editor context associated with the global context.
             raw: unsafe { sys::imnodes_EditorContextCreate() },
+            events: EventTracker::default(),
+            frame: FrameState::default(),
+            canvas_origin: [0.0, 0.0],
+            hovered: None,
+            pending_link_drop: None,
+            pending_context_menu: None,
+            link_drag: None,
+            style: None,
+            io: None,
+            context: Rc::clone(&self.context),
         }
     }
 }
 
-impl Drop for Context {
-    /// Destroys the global imnodes context.
+/// Keeps a [`Context`] current while alive.
+///
+/// Created by [`Context::make_current`]. Restores the previously current imnodes and imgui
+/// contexts when dropped.
+#[derive(Debug)]
+pub struct CurrentContextGuard<'a> {
+    previous: *mut sys::ImNodesContext,
+    previous_imgui: *mut imgui::sys::ImGuiContext,
+    // The imgui context imnodes was bound to before, see `bind_imgui`.
+    previous_bound_imgui: *mut imgui::sys::ImGuiContext,
+    context: &'a Context,
+}
+
+impl Drop for CurrentContextGuard<'_> {
+    /// Restores the previously current contexts.
     fn drop(&mut self) {
-        // Safety: Destroys the global context created by `imnodes_CreateContext`.
-        unsafe { sys::imnodes_DestroyContext(self.context) }
+        debug_assert!(
+            std::thread::panicking() || self.context.is_current(),
+            "The current imnodes context was changed while a `CurrentContextGuard` was alive"
+        );
+        // Safety: C API calls. Restores the pointers that were current when the guard was created.
+        unsafe { sys::imnodes_SetCurrentContext(self.previous) };
+        if !self.previous_bound_imgui.is_null() {
+            let _ = bind_imgui(self.previous_bound_imgui);
+        }
+        // Binding imnodes may switch the current imgui context, so it is restored last.
+        // Safety: C API call. See above.
+        unsafe { imgui::sys::igSetCurrentContext(self.previous_imgui) };
+    }
+}
+
+thread_local! {
+    // The imgui context imnodes was last bound to, imnodes has no getter for it.
+    static BOUND_IMGUI: Cell<*mut imgui::sys::ImGuiContext> =
+        const { Cell::new(std::ptr::null_mut()) };
+}
+
+/// Binds imnodes to the `imgui` context and returns the imgui context it was bound to before,
+/// or null if it was never bound on this thread.
+fn bind_imgui(imgui: *mut imgui::sys::ImGuiContext) -> *mut imgui::sys::ImGuiContext {
+    // Safety: C API call. imgui-sys and imnodes-sys define their own (but compatible)
+    // `ImGuiContext` types.
+    unsafe { sys::imnodes_SetImGuiContext(imgui.cast()) };
+    BOUND_IMGUI.replace(imgui)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Mutex, PoisonError};
+
+    // imgui allows only one active context per process.
+    static IMGUI: Mutex<()> = Mutex::new(());
+
+    /// Saves and loads a state, which needs both the editor and its global context.
+    fn use_editor(editor: &EditorContext) {
+        let state = editor.save_editor_state_to_string().unwrap();
+        editor.load_editor_state_from_string(&state).unwrap();
+        let _ = editor.get_panning();
+    }
+
+    #[test]
+    fn context_dropped_before_editors() {
+        let _lock = IMGUI.lock().unwrap_or_else(PoisonError::into_inner);
+        let _imgui = imgui::Context::create();
+        let context = Context::new();
+        let first = context.create_editor();
+        let second = context.create_editor();
+
+        drop(context);
+        use_editor(&first);
+        use_editor(&second);
+        drop(first);
+        use_editor(&second);
+        drop(second);
+    }
+
+    #[test]
+    fn editors_dropped_before_context() {
+        let _lock = IMGUI.lock().unwrap_or_else(PoisonError::into_inner);
+        let _imgui = imgui::Context::create();
+        let context = Context::new();
+        let first = context.create_editor();
+        let second = context.create_editor();
+
+        use_editor(&first);
+        drop(first);
+        use_editor(&second);
+        drop(second);
+        let third = context.create_editor();
+        use_editor(&third);
+        drop(context);
+        use_editor(&third);
+    }
+
+    #[test]
+    fn editor_calls_keep_the_current_context() {
+        let _lock = IMGUI.lock().unwrap_or_else(PoisonError::into_inner);
+        let _imgui = imgui::Context::create();
+        let first = Context::new();
+        let second = Context::new();
+        let editor = second.create_editor();
+
+        let _ = editor.get_panning();
+        editor.clear_node_selection();
+        assert!(first.is_current());
+    }
+
+    #[test]
+    fn scales_the_style_from_the_unscaled_one() {
+        let _lock = IMGUI.lock().unwrap_or_else(PoisonError::into_inner);
+        let _imgui = imgui::Context::create();
+        let context = Context::new();
+        let mut style = Style::default();
+        let _ = style.set_grid_spacing(0.3).set_link_thickness(1.1);
+        context.set_unscaled_style(style);
+
+        for factor in [1.1, 1.7, 0.3, 2.9, 1.3] {
+            for _ in 0..100 {
+                context.set_style_scale_factor(factor);
+            }
+        }
+        context.set_style_scale_factor(1.0);
+        // Safety: The style lives as long as the context.
+        let shared = unsafe { &*context.context.style() };
+        assert_eq!(shared.grid_spacing(), 0.3);
+        assert_eq!(shared.link_thickness(), 1.1);
+
+        context.set_style_scale_factor(3.0);
+        let mut expected = Style::default();
+        let _ = expected.scale_all_sizes(3.0);
+        context.set_unscaled_style(Style::default());
+        // Safety: See above.
+        let shared = unsafe { &*context.context.style() };
+        assert_eq!(shared.grid_spacing(), expected.grid_spacing());
+        assert_eq!(shared.node_padding().x, expected.node_padding().x);
+    }
+
+    #[test]
+    fn rejects_malformed_editor_states() {
+        let _lock = IMGUI.lock().unwrap_or_else(PoisonError::into_inner);
+        let _imgui = imgui::Context::create();
+        let context = Context::new();
+        let editor = context.create_editor();
+
+        for data in ["[node.x]\norigin=1,2\n", "[editor]\npanning=1\n"] {
+            assert!(matches!(
+                editor.load_editor_state_from_string(data),
+                Err(Error::Malformed(_))
+            ));
+            assert!(matches!(
+                editor.load_current_editor_state_from_string(data),
+                Err(Error::Malformed(_))
+            ));
+        }
+        editor
+            .load_editor_state_from_string("; comment\n[editor]\npanning=1,2\n[window]\n")
+            .unwrap();
+    }
+
+    #[test]
+    fn missing_saved_state_is_an_error() {
+        // Safety: A null pointer is allowed.
+        let result = unsafe { ini_string(std::ptr::null(), 0) };
+        assert!(matches!(result, Err(Error::NotSaved)));
+
+        let saved = b"[editor]\npanning=0,0\n\0";
+        // Safety: `saved` is valid for its length.
+        let result = unsafe { ini_string(saved.as_ptr().cast(), saved.len()) };
+        assert_eq!(result.unwrap(), "[editor]\npanning=0,0\n");
+    }
+
+    #[test]
+    fn nested_guards_restore_the_outer_context() {
+        let _lock = IMGUI.lock().unwrap_or_else(PoisonError::into_inner);
+        let _imgui = imgui::Context::create();
+        // Safety: Only reads the current imgui context.
+        let imgui = unsafe { imgui::sys::igGetCurrentContext() };
+        let first = Context::new();
+        let second = Context::new();
+        assert!(first.is_current());
+
+        {
+            let _outer = second.make_current();
+            assert!(second.is_current());
+            {
+                let _inner = first.make_current();
+                assert!(first.is_current());
+            }
+            assert!(second.is_current());
+        }
+        assert!(first.is_current());
+        // Safety: See above.
+        assert_eq!(unsafe { imgui::sys::igGetCurrentContext() }, imgui);
+    }
+
+    #[test]
+    fn is_current_after_guard_dropped() {
+        let _lock = IMGUI.lock().unwrap_or_else(PoisonError::into_inner);
+        let _imgui = imgui::Context::create();
+        let first = Context::new();
+        let second = Context::new();
+
+        drop(first.make_current());
+        assert!(first.is_current());
+        drop(second.make_current());
+        assert!(first.is_current());
+        assert!(!second.is_current());
+    }
+
+    #[test]
+    #[should_panic(expected = "is not the active one")]
+    fn make_current_checks_the_imgui_context() {
+        let _lock = IMGUI.lock().unwrap_or_else(PoisonError::into_inner);
+        let imgui = imgui::Context::create();
+        let context = Context::new();
+        let _suspended = imgui.suspend();
+        let _other_imgui = imgui::Context::create();
+
+        let _current = context.make_current();
     }
 }
@@ -0,0 +1,366 @@
+/*!
+A structured view of the editor state imnodes saves as an INI string.
+
+imnodes stores the panning of an editor and the grid space origin of every node it knows:
+
+```ini
+[editor]
+panning=0,0
+
+[node.1]
+origin=40,80
+```
+
+[`EditorState`] parses and writes this format, so layouts can be stored in project files,
+merged or adjusted without a live imgui frame.
+*/
+
+use crate::{EditorContext, NodeId};
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::str::{FromStr, Utf8Error};
+
+/// The panning of an editor and the positions of its nodes, as saved by imnodes.
+///
+/// Parse it from the string of [`EditorContext::save_editor_state_to_string`] with
+/// [`str::parse`] and turn it back into that format with [`ToString::to_string`].
+///
+/// imnodes stores whole pixels only, so positions are truncated towards zero when written.
+/// Positions outside the range of `i32` are clamped to it and NaN is written as `0`.
+///
+/// ```no_run
+/// # let editor_context: imnodes::EditorContext = unimplemented!();
+/// # let node: imnodes::NodeId = unimplemented!();
+/// let mut state = editor_context.editor_state()?;
+/// state.set_node_position(node, [100.0, 50.0]);
+/// editor_context.load_editor_state(&state);
+/// # Ok::<(), imnodes::Error>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EditorState {
+    /// The panning of the editor, the offset of the grid origin from the top left corner of the
+    /// editor canvas.
+    pub panning: [f32; 2],
+    /// The grid space position of the top left corner of each node, in the order imnodes saved them.
+    pub nodes: Vec<(NodeId, [f32; 2])>,
+}
+
+impl EditorState {
+    /// Returns the grid space position of `node`, if the state contains it.
+    #[must_use]
+    pub fn node_position(&self, node: NodeId) -> Option<[f32; 2]> {
+        self.nodes
+            .iter()
+            .find_map(|&(id, position)| (id == node).then_some(position))
+    }
+
+    /// Sets the grid space position of `node`, adding it if the state does not contain it yet.
+    pub fn set_node_position(&mut self, node: NodeId, position: [f32; 2]) -> &mut Self {
+        match self.nodes.iter_mut().find(|(id, _)| *id == node) {
+            Some((_, old)) => *old = position,
+            None => self.nodes.push((node, position)),
+        }
+        self
+    }
+
+    /// Removes `node`, returning its position if the state contained it.
+    pub fn remove_node(&mut self, node: NodeId) -> Option<[f32; 2]> {
+        let index = self.nodes.iter().position(|(id, _)| *id == node)?;
+        Some(self.nodes.remove(index).1)
+    }
+
+    /// Adds the node positions of `other`, replacing the positions of nodes both states contain.
+    ///
+    /// The panning of `self` is kept.
+    pub fn merge(&mut self, other: &Self) -> &mut Self {
+        for &(node, position) in &other.nodes {
+            let _ = self.set_node_position(node, position);
+        }
+        self
+    }
+}
+
+impl fmt::Display for EditorState {
+    /// Writes the state in the INI format imnodes loads.
+    ///
+    /// Values outside the range of `i32` are clamped to it and NaN is written as `0`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [x, y] = self.panning.map(ini_integer);
+        write!(f, "[editor]\npanning={x},{y}\n")?;
+        for (node, position) in &self.nodes {
+            let [x, y] = position.map(ini_integer);
+            write!(f, "\n[node.{}]\norigin={x},{y}\n", node.id)?;
+        }
+        Ok(())
+    }
+}
+
+/// Converts `value` for writing, imnodes reads the values with `%i`.
+fn ini_integer(value: f32) -> i32 {
+    if value.is_nan() {
+        0
+    } else {
+        // `as` truncates towards zero and clamps to the range of `i32`.
+        value as i32
+    }
+}
+
+impl FromStr for EditorState {
+    type Err = ParseEditorStateError;
+
+    /// Parses the INI format imnodes saves.
+    ///
+    /// Like imnodes, lines starting with `;` are comments and unknown sections and keys are
+    /// ignored. Invalid node IDs and values of `panning` and `origin` are errors.
+    ///
+    /// A node section without an `origin` places the node at the grid origin, like imnodes does.
+    /// Repeated node sections replace the earlier ones.
+    fn from_str(ini: &str) -> Result<Self, Self::Err> {
+        enum Section {
+            Unknown,
+            Editor,
+            Node(NodeId),
+        }
+
+        let mut state = Self::default();
+        let mut section = Section::Unknown;
+        // The strings returned by imnodes may include the terminating NUL.
+        for (index, line) in ini.trim_end_matches('\0').lines().enumerate() {
+            let error = |reason| ParseEditorStateError {
+                line: index + 1,
+                reason,
+            };
+            let line = line.trim();
+            if line.is_empty() || line.starts_with(';') {
+                continue;
+            }
+
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                section = if name == "editor" {
+                    Section::Editor
+                } else if let Some(id) = name.strip_prefix("node.") {
+                    let node = NodeId {
+                        id: id.parse().map_err(|_| error("invalid node ID"))?,
+                    };
+                    let _ = state.set_node_position(node, [0.0, 0.0]);
+                    Section::Node(node)
+                } else {
+                    Section::Unknown
+                };
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let value = || parse_pair(value.trim()).ok_or(error("expected two numbers `x,y`"));
+            match (&section, key.trim()) {
+                (Section::Editor, "panning") => state.panning = value()?,
+                (Section::Node(node), "origin") => {
+                    let _ = state.set_node_position(*node, value()?);
+                }
+                _ => {}
+            }
+        }
+        Ok(state)
+    }
+}
+
+/// Parses `x,y`.
+fn parse_pair(value: &str) -> Option<[f32; 2]> {
+    let (x, y) = value.split_once(',')?;
+    Some([x.trim().parse().ok()?, y.trim().parse().ok()?])
+}
+
+/// Reason why a string could not be parsed as an [`EditorState`].
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct ParseEditorStateError {
+    line: usize,
+    reason: &'static str,
+}
+
+impl ParseEditorStateError {
+    /// Returns the line of the error, starting at 1.
+    #[must_use]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+impl fmt::Display for ParseEditorStateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid editor state in line {}: {}",
+            self.line, self.reason
+        )
+    }
+}
+
+impl std::error::Error for ParseEditorStateError {}
+
+/// Reason why saving or loading an editor state failed.
+#[derive(Debug)]
+pub enum Error {
+    /// Reading or writing a file failed.
+    Io(io::Error),
+    /// The file to load the state from does not exist.
+    NotFound(PathBuf),
+    /// The saved state is not valid UTF-8.
+    InvalidUtf8(Utf8Error),
+    /// The saved state is not in the format imnodes saves.
+    Malformed(ParseEditorStateError),
+    /// imnodes returned no saved state.
+    NotSaved,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "cannot access the editor state file: {error}"),
+            Self::NotFound(path) => {
+                write!(f, "editor state file `{}` does not exist", path.display())
+            }
+            Self::InvalidUtf8(error) => write!(f, "editor state is not valid UTF-8: {error}"),
+            Self::Malformed(error) => error.fmt(f),
+            Self::NotSaved => write!(f, "imnodes returned no saved editor state"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::NotFound(_) => None,
+            Self::InvalidUtf8(error) => Some(error),
+            Self::Malformed(error) => Some(error),
+            Self::NotSaved => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<Utf8Error> for Error {
+    fn from(error: Utf8Error) -> Self {
+        Self::InvalidUtf8(error)
+    }
+}
+
+impl From<ParseEditorStateError> for Error {
+    fn from(error: ParseEditorStateError) -> Self {
+        Self::Malformed(error)
+    }
+}
+
+impl EditorContext {
+    /// Returns the panning and node positions of this editor.
+    pub fn editor_state(&self) -> Result<EditorState, Error> {
+        Ok(self.save_editor_state_to_string()?.parse()?)
+    }
+
+    /// Restores the panning and node positions of this editor.
+    ///
+    /// Nodes missing from `state` keep their position.
+    pub fn load_editor_state(&self, state: &EditorState) {
+        self.load_valid_editor_state(&state.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: i32) -> NodeId {
+        NodeId { id }
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut state = EditorState {
+            panning: [12.0, -7.0],
+            nodes: Vec::new(),
+        };
+        let _ = state
+            .set_node_position(node(1), [40.0, 80.0])
+            .set_node_position(node(2), [-3.0, 0.0]);
+
+        let written = state.to_string();
+        assert_eq!(
+            written,
+            "[editor]\npanning=12,-7\n\n[node.1]\norigin=40,80\n\n[node.2]\norigin=-3,0\n"
+        );
+        assert_eq!(written.parse::<EditorState>(), Ok(state));
+    }
+
+    #[test]
+    fn writes_whole_pixels() {
+        let state = EditorState {
+            panning: [1.9, -1.9],
+            nodes: vec![
+                (node(1), [f32::NAN, 1e20]),
+                (node(2), [-1e20, f32::INFINITY]),
+            ],
+        };
+        assert_eq!(
+            state.to_string(),
+            "[editor]\npanning=1,-1\n\n[node.1]\norigin=0,2147483647\n\n\
+             [node.2]\norigin=-2147483648,2147483647\n"
+        );
+    }
+
+    #[test]
+    fn skips_comments_and_unknown_entries() {
+        let state: EditorState = "; saved by app\n\
+                                  [editor]\n\
+                                  ; panning=9,9\n\
+                                  panning=1,2\n\
+                                  zoom=2\n\
+                                  \n\
+                                  [window]\n\
+                                  origin=5,5\n\
+                                  [node.3]\n\
+                                  origin=4,5\n\
+                                  selected\n"
+            .parse()
+            .unwrap();
+        assert_eq!(state.panning, [1.0, 2.0]);
+        assert_eq!(state.nodes, vec![(node(3), [4.0, 5.0])]);
+    }
+
+    #[test]
+    fn accepts_crlf_and_trailing_nul() {
+        let state: EditorState = "[editor]\r\npanning=1,2\r\n\r\n[node.1]\r\norigin=3,4\r\n\0"
+            .parse()
+            .unwrap();
+        assert_eq!(state.panning, [1.0, 2.0]);
+        assert_eq!(state.node_position(node(1)), Some([3.0, 4.0]));
+    }
+
+    #[test]
+    fn parses_negative_ids() {
+        let state: EditorState = "[node.-5]\norigin=-1,-2\n[node.-6]\n".parse().unwrap();
+        assert_eq!(
+            state.nodes,
+            vec![(node(-5), [-1.0, -2.0]), (node(-6), [0.0, 0.0])]
+        );
+        assert!(state.to_string().contains("[node.-5]\norigin=-1,-2\n"));
+    }
+
+    #[test]
+    fn reports_invalid_values() {
+        let error = "[editor]\npanning=1,2\n[node.x]\n"
+            .parse::<EditorState>()
+            .unwrap_err();
+        assert_eq!(error.line(), 3);
+
+        let error = "[editor]\npanning=1\n".parse::<EditorState>().unwrap_err();
+        assert_eq!(error.line(), 2);
+    }
+}
@@ -0,0 +1,485 @@
+/*!
+Evaluates a [`Graph`] as a dataflow program, enabled with the `graph` feature.
+
+Every node implements [`Evaluate`], computing the values of its output pins from the values
+linked to its input pins. An [`Evaluator`] runs the nodes in topological order, caches their
+outputs and records the nodes which could not be evaluated.
+
+```
+use imnodes::evaluation::{Evaluate, Evaluator};
+use imnodes::graph::Graph;
+
+enum Op {
+    Constant(f32),
+    Double,
+}
+
+impl Evaluate for Op {
+    type Value = f32;
+    type Error = &'static str;
+
+    fn evaluate(&mut self, inputs: &[Option<f32>]) -> Result<Vec<f32>, Self::Error> {
+        match self {
+            Self::Constant(value) => Ok(vec![*value]),
+            Self::Double => match inputs {
+                [Some(value)] => Ok(vec![2.0 * value]),
+                _ => Err("the input of Double needs to be linked"),
+            },
+        }
+    }
+}
+
+let mut graph = Graph::<Op>::new();
+let constant = graph.add_node(Op::Constant(1.5));
+let constant_output = graph.add_output(constant).unwrap();
+let double = graph.add_node(Op::Double);
+let double_input = graph.add_input(double).unwrap();
+let double_output = graph.add_output(double).unwrap();
+
+let mut evaluator = Evaluator::new();
+evaluator.run(&mut graph);
+assert_eq!(evaluator.output(double_output), None);
+for (node, error) in evaluator.errors() {
+    println!("{node:?}: {error}");
+}
+
+graph.add_link(constant_output, double_input, ())?;
+evaluator.run(&mut graph);
+assert_eq!(evaluator.output(double_output), Some(&3.0));
+# Ok::<(), imnodes::graph::LinkRejection>(())
+```
+*/
+
+use crate::graph::Graph;
+use crate::{NodeId, OutputPinId};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// A node which computes the values of its outputs from the values of its inputs.
+pub trait Evaluate {
+    /// The values passed along links.
+    type Value: Clone + PartialEq;
+    /// Why a node could not be evaluated.
+    type Error;
+
+    /// Returns one value per output pin, in the order of [`crate::graph::GraphNode::outputs`].
+    ///
+    /// `inputs` has one value per input pin in the order of
+    /// [`crate::graph::GraphNode::inputs`], `None` for inputs without a link.
+    fn evaluate(&mut self, inputs: &[Option<Self::Value>])
+    -> Result<Vec<Self::Value>, Self::Error>;
+
+    /// Returns whether the outputs can change although the inputs did not, e.g. for a node
+    /// reading the time. Such nodes are evaluated on every [`Evaluator::run`].
+    fn is_volatile(&self) -> bool {
+        false
+    }
+}
+
+/// Reason why [`Evaluator::run`] could not evaluate a node.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NodeError<E> {
+    /// [`Evaluate::evaluate`] failed.
+    Failed(E),
+    /// [`Evaluate::evaluate`] returned a different number of values than the node has outputs.
+    OutputCount {
+        /// The number of output pins.
+        expected: usize,
+        /// The number of values returned.
+        actual: usize,
+    },
+    /// The node is part of a cycle.
+    Cycle,
+    /// A node linked to the inputs could not be evaluated.
+    Upstream(NodeId),
+}
+
+impl<E: fmt::Display> fmt::Display for NodeError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Failed(error) => error.fmt(f),
+            Self::OutputCount { expected, actual } => write!(
+                f,
+                "evaluation returned {actual} values for {expected} outputs"
+            ),
+            Self::Cycle => f.write_str("the node is part of a cycle"),
+            Self::Upstream(node) => write!(f, "the input node {node:?} could not be evaluated"),
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> std::error::Error for NodeError<E> {}
+
+/// Runs the nodes of a [`Graph`] and caches the values of their outputs.
+///
+/// A node is only evaluated again if the values of its inputs changed, it is
+/// [volatile](Evaluate::is_volatile) or it was [invalidated](Evaluator::invalidate), e.g.
+/// after the user edited its parameters.
+#[derive(Debug)]
+pub struct Evaluator<N: Evaluate> {
+    outputs: HashMap<OutputPinId, N::Value>,
+    // The inputs each node was last evaluated with.
+    inputs: HashMap<NodeId, Vec<Option<N::Value>>>,
+    errors: HashMap<NodeId, NodeError<N::Error>>,
+    invalidated: HashSet<NodeId>,
+}
+
+impl<N: Evaluate> Default for Evaluator<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N: Evaluate> Evaluator<N> {
+    /// Creates an evaluator without cached values.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            outputs: HashMap::new(),
+            inputs: HashMap::new(),
+            errors: HashMap::new(),
+            invalidated: HashSet::new(),
+        }
+    }
+
+    /// Evaluates `node` on the next [`Evaluator::run`], even if its inputs did not change.
+    pub fn invalidate(&mut self, node: NodeId) {
+        self.invalidated.insert(node);
+    }
+
+    /// Returns the value of `pin` computed by the last [`Evaluator::run`].
+    ///
+    /// Returns `None` if the node of the pin could not be evaluated.
+    #[must_use]
+    pub fn output(&self, pin: OutputPinId) -> Option<&N::Value> {
+        self.outputs.get(&pin)
+    }
+
+    /// Returns why `node` could not be evaluated by the last [`Evaluator::run`].
+    #[must_use]
+    pub fn error(&self, node: NodeId) -> Option<&NodeError<N::Error>> {
+        self.errors.get(&node)
+    }
+
+    /// Returns all nodes the last [`Evaluator::run`] could not evaluate.
+    pub fn errors(&self) -> impl Iterator<Item = (NodeId, &NodeError<N::Error>)> {
+        self.errors.iter().map(|(&node, error)| (node, error))
+    }
+
+    /// Evaluates all nodes of `graph` whose outputs may have changed, inputs first.
+    ///
+    /// An input linked to several outputs gets the value of the link added first. Nodes in a
+    /// cycle and nodes depending on a node which could not be evaluated are not evaluated.
+    pub fn run<L>(&mut self, graph: &mut Graph<N, L>) {
+        self.errors.clear();
+        let topology = graph.topology();
+        let indices: HashMap<NodeId, usize> = graph
+            .nodes()
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (node.id(), index))
+            .collect();
+
+        // Components come sinks first, so the nodes are visited in reverse.
+        for component in topology.strongly_connected_components().into_iter().rev() {
+            let cyclic =
+                component.len() > 1 || topology.successors(component[0]).contains(&component[0]);
+            for node in component {
+                let Some(&index) = indices.get(&node) else {
+                    continue;
+                };
+                let error = if cyclic {
+                    Some(NodeError::Cycle)
+                } else {
+                    self.evaluate_node(graph, index).err()
+                };
+                if let Some(error) = error {
+                    for output in graph.nodes()[index].outputs() {
+                        self.outputs.remove(output);
+                    }
+                    self.inputs.remove(&node);
+                    self.errors.insert(node, error);
+                }
+            }
+        }
+
+        // Forget the values of removed nodes.
+        self.outputs.retain(|&pin, _| graph.pin(pin).is_some());
+        self.inputs.retain(|node, _| indices.contains_key(node));
+        self.invalidated.clear();
+    }
+
+    /// Evaluates the node at `index` if its outputs may have changed.
+    fn evaluate_node<L>(
+        &mut self,
+        graph: &mut Graph<N, L>,
+        index: usize,
+    ) -> Result<(), NodeError<N::Error>> {
+        let node = &graph.nodes()[index];
+        let mut inputs = Vec::with_capacity(node.inputs().len());
+        for &pin in node.inputs() {
+            let value = match graph.links_to(pin).next() {
+                Some(link) if self.errors.contains_key(&link.start_node()) => {
+                    return Err(NodeError::Upstream(link.start_node()));
+                }
+                Some(link) => self.outputs.get(&link.start_pin()).cloned(),
+                None => None,
+            };
+            inputs.push(value);
+        }
+
+        let id = node.id();
+        let outputs = node.outputs().to_vec();
+        let node = &mut graph.nodes_mut()[index];
+        let unchanged = !node.data.is_volatile()
+            && !self.invalidated.contains(&id)
+            && self.inputs.get(&id) == Some(&inputs)
+            && outputs.iter().all(|pin| self.outputs.contains_key(pin));
+        if unchanged {
+            return Ok(());
+        }
+
+        let values = node.data.evaluate(&inputs).map_err(NodeError::Failed)?;
+        if values.len() != outputs.len() {
+            return Err(NodeError::OutputCount {
+                expected: outputs.len(),
+                actual: values.len(),
+            });
+        }
+        self.outputs.extend(outputs.into_iter().zip(values));
+        self.inputs.insert(id, inputs);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InputPinId;
+
+    enum Op {
+        Constant(f32),
+        Sum,
+        // Counts up on every evaluation.
+        Counter(f32),
+        Fail,
+        // Returns the given number of values.
+        Values(usize),
+    }
+
+    struct TestNode {
+        op: Op,
+        evaluations: usize,
+    }
+
+    impl Evaluate for TestNode {
+        type Value = f32;
+        type Error = &'static str;
+
+        fn evaluate(&mut self, inputs: &[Option<f32>]) -> Result<Vec<f32>, Self::Error> {
+            self.evaluations += 1;
+            match &mut self.op {
+                Op::Constant(value) => Ok(vec![*value]),
+                Op::Sum => Ok(vec![inputs.iter().flatten().sum()]),
+                Op::Counter(count) => {
+                    *count += 1.0;
+                    Ok(vec![*count])
+                }
+                Op::Fail => Err("failed"),
+                Op::Values(count) => Ok(vec![0.0; *count]),
+            }
+        }
+
+        fn is_volatile(&self) -> bool {
+            matches!(self.op, Op::Counter(_))
+        }
+    }
+
+    struct Pins {
+        node: NodeId,
+        inputs: Vec<InputPinId>,
+        output: OutputPinId,
+    }
+
+    /// Adds a node with `inputs` inputs and one output.
+    fn add(graph: &mut Graph<TestNode>, op: Op, inputs: usize) -> Pins {
+        let node = graph.add_node(TestNode { op, evaluations: 0 });
+        Pins {
+            node,
+            inputs: (0..inputs)
+                .map(|_| graph.add_input(node).unwrap())
+                .collect(),
+            output: graph.add_output(node).unwrap(),
+        }
+    }
+
+    fn link(graph: &mut Graph<TestNode>, from: &Pins, to: &Pins, input: usize) {
+        let _ = graph.add_link(from.output, to.inputs[input], ()).unwrap();
+    }
+
+    fn evaluations(graph: &Graph<TestNode>, pins: &Pins) -> usize {
+        graph.node(pins.node).unwrap().data.evaluations
+    }
+
+    #[test]
+    fn caches_outputs_of_unchanged_inputs() {
+        let mut graph = Graph::new();
+        let a = add(&mut graph, Op::Constant(1.0), 0);
+        let b = add(&mut graph, Op::Constant(2.0), 0);
+        let sum = add(&mut graph, Op::Sum, 2);
+        link(&mut graph, &a, &sum, 0);
+        link(&mut graph, &b, &sum, 1);
+
+        let mut evaluator = Evaluator::new();
+        evaluator.run(&mut graph);
+        evaluator.run(&mut graph);
+        assert_eq!(evaluator.output(sum.output), Some(&3.0));
+        for pins in [&a, &b, &sum] {
+            assert_eq!(evaluations(&graph, pins), 1);
+        }
+
+        // Unlinking an input changes the inputs of the sum.
+        let link = graph.links_to(sum.inputs[1]).next().unwrap().id();
+        let _ = graph.remove_link(link);
+        evaluator.run(&mut graph);
+        assert_eq!(evaluator.output(sum.output), Some(&1.0));
+        assert_eq!(evaluations(&graph, &sum), 2);
+        assert_eq!(evaluations(&graph, &b), 1);
+    }
+
+    #[test]
+    fn invalidated_nodes_are_evaluated_again() {
+        let mut graph = Graph::new();
+        let a = add(&mut graph, Op::Constant(1.0), 0);
+        let sum = add(&mut graph, Op::Sum, 1);
+        link(&mut graph, &a, &sum, 0);
+        let mut evaluator = Evaluator::new();
+        evaluator.run(&mut graph);
+
+        // Edits are only picked up after invalidating the node.
+        graph.node_mut(a.node).unwrap().data.op = Op::Constant(4.0);
+        evaluator.run(&mut graph);
+        assert_eq!(evaluator.output(sum.output), Some(&1.0));
+        evaluator.invalidate(a.node);
+        evaluator.run(&mut graph);
+        assert_eq!(evaluator.output(sum.output), Some(&4.0));
+        assert_eq!(evaluations(&graph, &a), 2);
+        assert_eq!(evaluations(&graph, &sum), 2);
+
+        // A node evaluated to the same outputs does not change its successors.
+        evaluator.invalidate(a.node);
+        evaluator.run(&mut graph);
+        assert_eq!(evaluations(&graph, &a), 3);
+        assert_eq!(evaluations(&graph, &sum), 2);
+    }
+
+    #[test]
+    fn volatile_nodes_are_evaluated_on_every_run() {
+        let mut graph = Graph::new();
+        let counter = add(&mut graph, Op::Counter(0.0), 0);
+        let sum = add(&mut graph, Op::Sum, 1);
+        link(&mut graph, &counter, &sum, 0);
+        let mut evaluator = Evaluator::new();
+        for run in 1..=3 {
+            evaluator.run(&mut graph);
+            assert_eq!(evaluator.output(sum.output), Some(&(run as f32)));
+        }
+        assert_eq!(evaluations(&graph, &counter), 3);
+        assert_eq!(evaluations(&graph, &sum), 3);
+    }
+
+    #[test]
+    fn reports_wrong_output_counts() {
+        let mut graph = Graph::new();
+        let values = add(&mut graph, Op::Values(2), 0);
+        let mut evaluator = Evaluator::new();
+        evaluator.run(&mut graph);
+        assert_eq!(
+            evaluator.error(values.node),
+            Some(&NodeError::OutputCount {
+                expected: 1,
+                actual: 2,
+            })
+        );
+        assert_eq!(evaluator.output(values.output), None);
+    }
+
+    #[test]
+    fn reports_cycles() {
+        let mut graph = Graph::new();
+        let a = add(&mut graph, Op::Constant(1.0), 0);
+        let first = add(&mut graph, Op::Sum, 2);
+        let second = add(&mut graph, Op::Sum, 1);
+        let after = add(&mut graph, Op::Sum, 1);
+        let apart = add(&mut graph, Op::Sum, 1);
+        link(&mut graph, &a, &first, 0);
+        link(&mut graph, &first, &second, 0);
+        link(&mut graph, &second, &first, 1);
+        link(&mut graph, &second, &after, 0);
+        link(&mut graph, &a, &apart, 0);
+
+        let mut evaluator = Evaluator::new();
+        evaluator.run(&mut graph);
+        assert_eq!(evaluator.error(first.node), Some(&NodeError::Cycle));
+        assert_eq!(evaluator.error(second.node), Some(&NodeError::Cycle));
+        assert_eq!(
+            evaluator.error(after.node),
+            Some(&NodeError::Upstream(second.node))
+        );
+        assert_eq!(evaluator.output(apart.output), Some(&1.0));
+        assert_eq!(evaluator.errors().count(), 3);
+        assert_eq!(evaluations(&graph, &first), 0);
+    }
+
+    #[test]
+    fn propagates_errors_downstream() {
+        let mut graph = Graph::new();
+        let fail = add(&mut graph, Op::Fail, 0);
+        let first = add(&mut graph, Op::Sum, 1);
+        let second = add(&mut graph, Op::Sum, 1);
+        link(&mut graph, &fail, &first, 0);
+        link(&mut graph, &first, &second, 0);
+
+        let mut evaluator = Evaluator::new();
+        evaluator.run(&mut graph);
+        assert_eq!(
+            evaluator.error(fail.node),
+            Some(&NodeError::Failed("failed"))
+        );
+        assert_eq!(
+            evaluator.error(first.node),
+            Some(&NodeError::Upstream(fail.node))
+        );
+        assert_eq!(
+            evaluator.error(second.node),
+            Some(&NodeError::Upstream(first.node))
+        );
+        assert_eq!(evaluator.output(second.output), None);
+        assert_eq!(evaluations(&graph, &first), 0);
+
+        // Fixing the node clears the errors.
+        graph.node_mut(fail.node).unwrap().data.op = Op::Constant(2.0);
+        evaluator.run(&mut graph);
+        assert_eq!(evaluator.errors().count(), 0);
+        assert_eq!(evaluator.output(second.output), Some(&2.0));
+    }
+
+    #[test]
+    fn forgets_removed_nodes() {
+        let mut graph = Graph::new();
+        let a = add(&mut graph, Op::Constant(1.0), 0);
+        let sum = add(&mut graph, Op::Sum, 1);
+        link(&mut graph, &a, &sum, 0);
+        let mut evaluator = Evaluator::new();
+        evaluator.run(&mut graph);
+        assert_eq!(evaluator.output(a.output), Some(&1.0));
+
+        let _ = graph.remove_node(a.node);
+        evaluator.run(&mut graph);
+        assert_eq!(evaluator.output(a.output), None);
+        assert!(!evaluator.inputs.contains_key(&a.node));
+        assert_eq!(evaluator.outputs.len(), 1);
+        // The sum lost its input.
+        assert_eq!(evaluator.output(sum.output), Some(&0.0));
+    }
+}
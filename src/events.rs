@@ -0,0 +1,417 @@
+/*!
+Typed events describing what the user did in the node editor during a frame.
+
+Events are captured once right after the editor has ended and can be read with
+[`OuterScope::events`].
+*/
+
+use crate::{
+    FrameState, Link, LinkError, LinkId, NodeId, OuterScope, PinId, get_hovered_node, sys,
+};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// Something the user did in the node editor during the current frame.
+///
+/// Obtained from [`OuterScope::events`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum EditorEvent {
+    /// The user started dragging a new link from a pin.
+    LinkStarted(PinId),
+    /// The user dropped a link without connecting it to a pin.
+    ///
+    /// See [`crate::EditorContext::link_drop_popup`] to offer creating a node at the drop position.
+    LinkDropped {
+        /// The pin the link was dragged from.
+        from: PinId,
+        /// Where the link was dropped, in screen space.
+        screen_pos: [f32; 2],
+        /// Where the link was dropped, in grid space.
+        grid_pos: [f32; 2],
+        /// `true` if an existing link was detached from `from` and then dropped.
+        was_detached: bool,
+    },
+    /// The user created a new link. See [`OuterScope::links_created`].
+    LinkCreated(Link),
+    /// The user connected two pins which cannot be linked. See [`OuterScope::try_links_created`].
+    LinkRejected(LinkError),
+    /// The user detached an existing link. See [`OuterScope::get_destroyed_link`].
+    LinkDestroyed(LinkId),
+    /// The selection differs from the one of the previous frame.
+    /// See [`OuterScope::selection_changed`].
+    SelectionChanged(SelectionChange),
+    /// The user started dragging nodes.
+    NodeDragStarted {
+        /// The nodes being dragged.
+        ids: Vec<NodeId>,
+    },
+    /// The user moved nodes by dragging them.
+    ///
+    /// Emitted every frame in which the dragged nodes moved, between
+    /// [`EditorEvent::NodeDragStarted`] and [`EditorEvent::NodeDragEnded`].
+    NodesMoved {
+        /// The nodes which moved in this frame.
+        ids: Vec<NodeId>,
+        /// The distance moved since the last frame, in grid space.
+        delta: [f32; 2],
+    },
+    /// The user released the dragged nodes.
+    ///
+    /// Useful to record a single undo step for the whole drag.
+    NodeDragEnded {
+        /// All nodes moved during the drag.
+        ids: Vec<NodeId>,
+        /// The distance moved during the whole drag, in grid space.
+        delta: [f32; 2],
+    },
+    /// A node is hovered by the mouse.
+    NodeHovered(NodeId),
+    /// A link is hovered by the mouse.
+    LinkHovered(LinkId),
+    /// A pin is hovered by the mouse.
+    PinHovered(PinId),
+}
+
+/// How the selection changed compared to the previous frame.
+///
+/// Obtained from [`OuterScope::selection_changed`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SelectionChange {
+    /// All nodes selected now.
+    pub nodes: Vec<NodeId>,
+    /// All links selected now.
+    pub links: Vec<LinkId>,
+    /// Nodes selected now but not in the previous frame.
+    pub added_nodes: Vec<NodeId>,
+    /// Nodes selected in the previous frame but not anymore.
+    pub removed_nodes: Vec<NodeId>,
+    /// Links selected now but not in the previous frame.
+    pub added_links: Vec<LinkId>,
+    /// Links selected in the previous frame but not anymore.
+    pub removed_links: Vec<LinkId>,
+}
+
+impl SelectionChange {
+    /// Compares the current selection with the previous one.
+    /// Returns `None` if nothing changed.
+    fn between(
+        previous_nodes: &[NodeId],
+        previous_links: &[LinkId],
+        nodes: Vec<NodeId>,
+        links: Vec<LinkId>,
+    ) -> Option<Self> {
+        let change = Self {
+            added_nodes: difference(&nodes, previous_nodes),
+            removed_nodes: difference(previous_nodes, &nodes),
+            added_links: difference(&links, previous_links),
+            removed_links: difference(previous_links, &links),
+            nodes,
+            links,
+        };
+        let changed = !change.added_nodes.is_empty()
+            || !change.removed_nodes.is_empty()
+            || !change.added_links.is_empty()
+            || !change.removed_links.is_empty();
+        changed.then_some(change)
+    }
+}
+
+/// Per editor state needed to turn the imnodes queries into events.
+#[derive(Debug, Default)]
+pub(crate) struct EventTracker {
+    selected_nodes: Vec<NodeId>,
+    selected_links: Vec<LinkId>,
+    // Grid space positions of the nodes submitted in the previous frame.
+    node_positions: HashMap<NodeId, [f32; 2]>,
+    drag: Option<NodeDrag>,
+}
+
+/// A drag of nodes by the user which is still in progress.
+#[derive(Debug, Default)]
+struct NodeDrag {
+    ids: Vec<NodeId>,
+    delta: [f32; 2],
+}
+
+impl EventTracker {
+    /// Collects the events of the frame that was just ended.
+    ///
+    /// `mouse_pos` and `mouse_grid_pos` are the current mouse position in screen and grid space.
+    pub(crate) fn capture(
+        &mut self,
+        scope: &OuterScope,
+        frame: &FrameState,
+        mouse_pos: [f32; 2],
+        mouse_grid_pos: [f32; 2],
+    ) -> Vec<EditorEvent> {
+        let mut events = Vec::new();
+
+        if let Some(pin) = scope.from_where_link_started() {
+            events.push(EditorEvent::LinkStarted(pin));
+        }
+        if let Some(from) = scope.from_where_link_dropped(true) {
+            events.push(EditorEvent::LinkDropped {
+                from,
+                screen_pos: mouse_pos,
+                grid_pos: mouse_grid_pos,
+                was_detached: scope.from_where_link_dropped(false) != Some(from),
+            });
+        }
+        match scope.try_links_created() {
+            Some(Ok(link)) => events.push(EditorEvent::LinkCreated(link)),
+            Some(Err(error)) => events.push(EditorEvent::LinkRejected(error)),
+            None => {}
+        }
+        if let Some(link) = scope.get_destroyed_link() {
+            events.push(EditorEvent::LinkDestroyed(link));
+        }
+
+        if let Some(change) = SelectionChange::between(
+            &self.selected_nodes,
+            &self.selected_links,
+            scope.selected_nodes(),
+            scope.selected_links(),
+        ) {
+            self.selected_nodes.clone_from(&change.nodes);
+            self.selected_links.clone_from(&change.links);
+            events.push(EditorEvent::SelectionChanged(change));
+        }
+
+        self.capture_node_moves(frame, &mut events);
+
+        if let Some(node) = get_hovered_node() {
+            events.push(EditorEvent::NodeHovered(node));
+        }
+        if let Some(link) = scope.get_hovered_link() {
+            events.push(EditorEvent::LinkHovered(link));
+        }
+        if let Some(pin) = scope.get_hovered_pin() {
+            events.push(EditorEvent::PinHovered(pin));
+        }
+
+        events
+    }
+
+    /// Compares the node positions with the previous frame to detect nodes dragged by the user.
+    ///
+    /// Only selected nodes can be dragged, and only while the left mouse button is held down,
+    /// so nodes moved through [`crate::NodeHandle::set_position`] are usually not reported.
+    fn capture_node_moves(&mut self, frame: &FrameState, events: &mut Vec<EditorEvent>) {
+        let positions: Vec<(NodeId, [f32; 2])> = frame
+            .nodes
+            .iter()
+            .map(|&id| {
+                let mut pos = sys::ImVec2 { x: 0.0, y: 0.0 };
+                // Safety: C API call. `pos` is written to by the function.
+                unsafe {
+                    sys::imnodes_GetNodeGridSpacePos(core::ptr::from_mut(&mut pos), id.into())
+                };
+                (id, [pos.x, pos.y])
+            })
+            .collect();
+        // Safety: C API call. Only reads the mouse state of the current imgui context.
+        let dragging =
+            unsafe { imgui::sys::igIsMouseDown(imgui::sys::ImGuiMouseButton_Left as i32) };
+        // Safety: C API call. The editor is still current.
+        let is_selected = |id: NodeId| unsafe { sys::imnodes_IsNodeSelected(id.into()) };
+        self.track_node_moves(&positions, dragging, is_selected, events);
+    }
+
+    /// Detects dragged nodes from the grid space `positions` of the nodes submitted in this
+    /// frame, in submission order, and whether the left mouse button is held down.
+    fn track_node_moves(
+        &mut self,
+        positions: &[(NodeId, [f32; 2])],
+        dragging: bool,
+        is_selected: impl Fn(NodeId) -> bool,
+        events: &mut Vec<EditorEvent>,
+    ) {
+        let mut moved = Vec::new();
+        let mut delta = [0.0, 0.0];
+        if dragging {
+            // Iterate in submission order, the first moved node determines the delta.
+            for &(id, pos) in positions {
+                if let Some(previous) = self.node_positions.get(&id)
+                    && *previous != pos
+                    && is_selected(id)
+                {
+                    if moved.is_empty() {
+                        delta = [pos[0] - previous[0], pos[1] - previous[1]];
+                    }
+                    moved.push(id);
+                }
+            }
+        }
+        self.node_positions = positions.iter().copied().collect();
+
+        if !moved.is_empty() {
+            let drag = self.drag.get_or_insert_with(|| {
+                events.push(EditorEvent::NodeDragStarted { ids: moved.clone() });
+                NodeDrag::default()
+            });
+            for id in &moved {
+                if !drag.ids.contains(id) {
+                    drag.ids.push(*id);
+                }
+            }
+            drag.delta = [drag.delta[0] + delta[0], drag.delta[1] + delta[1]];
+            events.push(EditorEvent::NodesMoved { ids: moved, delta });
+        }
+
+        if !dragging && let Some(drag) = self.drag.take() {
+            events.push(EditorEvent::NodeDragEnded {
+                ids: drag.ids,
+                delta: drag.delta,
+            });
+        }
+    }
+}
+
+/// Returns the ids in `a` which are not in `b`, keeping the order of `a`.
+fn difference<T: Copy + Eq + Hash>(a: &[T], b: &[T]) -> Vec<T> {
+    let b: HashSet<_> = b.iter().collect();
+    a.iter().filter(|id| !b.contains(id)).copied().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: i32) -> NodeId {
+        NodeId { id }
+    }
+
+    fn link(id: i32) -> LinkId {
+        LinkId { id }
+    }
+
+    #[test]
+    fn unchanged_selection_is_no_change() {
+        assert_eq!(SelectionChange::between(&[], &[], vec![], vec![]), None);
+        assert_eq!(
+            SelectionChange::between(
+                &[node(1), node(2)],
+                &[link(3)],
+                vec![node(2), node(1)],
+                vec![link(3)],
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn reports_added_and_removed_nodes() {
+        let change =
+            SelectionChange::between(&[node(1), node(2)], &[], vec![node(2), node(3)], vec![])
+                .unwrap();
+        assert_eq!(
+            change,
+            SelectionChange {
+                nodes: vec![node(2), node(3)],
+                links: vec![],
+                added_nodes: vec![node(3)],
+                removed_nodes: vec![node(1)],
+                added_links: vec![],
+                removed_links: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn reports_added_and_removed_links() {
+        let change = SelectionChange::between(
+            &[node(1)],
+            &[link(1), link(2)],
+            vec![node(1)],
+            vec![link(3), link(1)],
+        )
+        .unwrap();
+        assert_eq!(
+            change,
+            SelectionChange {
+                nodes: vec![node(1)],
+                links: vec![link(3), link(1)],
+                added_nodes: vec![],
+                removed_nodes: vec![],
+                added_links: vec![link(3)],
+                removed_links: vec![link(2)],
+            }
+        );
+
+        let cleared = SelectionChange::between(&[], &[link(1)], vec![], vec![]).unwrap();
+        assert_eq!(cleared.removed_links, vec![link(1)]);
+        assert!(cleared.links.is_empty());
+    }
+
+    /// Feeds one frame of node positions to `tracker`, with nodes 1 and 2 selected.
+    fn frame(
+        tracker: &mut EventTracker,
+        positions: &[(i32, [f32; 2])],
+        dragging: bool,
+    ) -> Vec<EditorEvent> {
+        let positions: Vec<_> = positions.iter().map(|&(id, pos)| (node(id), pos)).collect();
+        let mut events = Vec::new();
+        tracker.track_node_moves(&positions, dragging, |id| id.id <= 2, &mut events);
+        events
+    }
+
+    #[test]
+    fn tracks_a_drag_across_frames() {
+        let mut tracker = EventTracker::default();
+        let start = [(1, [0.0, 0.0]), (2, [10.0, 0.0]), (3, [50.0, 50.0])];
+        assert_eq!(frame(&mut tracker, &start, false), vec![]);
+        // Pressing the mouse button without moving anything is no drag.
+        assert_eq!(frame(&mut tracker, &start, true), vec![]);
+
+        let moved = [(1, [2.0, 1.0]), (2, [12.0, 1.0]), (3, [50.0, 50.0])];
+        assert_eq!(
+            frame(&mut tracker, &moved, true),
+            vec![
+                EditorEvent::NodeDragStarted {
+                    ids: vec![node(1), node(2)]
+                },
+                EditorEvent::NodesMoved {
+                    ids: vec![node(1), node(2)],
+                    delta: [2.0, 1.0],
+                },
+            ]
+        );
+
+        // Holding the nodes still keeps the drag going without moves.
+        assert_eq!(frame(&mut tracker, &moved, true), vec![]);
+
+        let moved_again = [(1, [5.0, -1.0]), (2, [15.0, -1.0]), (3, [50.0, 50.0])];
+        assert_eq!(
+            frame(&mut tracker, &moved_again, true),
+            vec![EditorEvent::NodesMoved {
+                ids: vec![node(1), node(2)],
+                delta: [3.0, -2.0],
+            }]
+        );
+
+        assert_eq!(
+            frame(&mut tracker, &moved_again, false),
+            vec![EditorEvent::NodeDragEnded {
+                ids: vec![node(1), node(2)],
+                delta: [5.0, -1.0],
+            }]
+        );
+        assert_eq!(frame(&mut tracker, &moved_again, false), vec![]);
+    }
+
+    #[test]
+    fn ignores_unselected_and_new_nodes() {
+        let mut tracker = EventTracker::default();
+        let _ = frame(&mut tracker, &[(3, [0.0, 0.0])], false);
+        // Node 3 is not selected and node 1 was not submitted in the previous frame.
+        assert_eq!(
+            frame(&mut tracker, &[(3, [5.0, 5.0]), (1, [7.0, 7.0])], true),
+            vec![]
+        );
+        // Moving without the mouse button held down, e.g. through `set_position`, is no drag.
+        assert_eq!(
+            frame(&mut tracker, &[(3, [5.0, 5.0]), (1, [9.0, 9.0])], false),
+            vec![]
+        );
+    }
+}
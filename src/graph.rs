@@ -0,0 +1,744 @@
+/*!
+A retained-mode graph which owns its nodes, pins and links, enabled with the `graph` feature.
+
+[`Graph`] allocates all IDs itself, draws its nodes through a [`NodeRenderer`] and its links
+inside an [`EditorScope`], and applies the links the user created or detached afterwards.
+New links are validated against the [`LinkPolicy`] of the graph.
+
+```no_run
+# let ui: &imgui::Ui = unimplemented!();
+# let mut editor_context: imnodes::EditorContext = unimplemented!();
+use imnodes::graph::{Graph, GraphEvent, GraphNode, LinkPolicy};
+use imnodes::{NodeScope, PinShape};
+
+let mut graph = Graph::<&str>::new();
+let _ = graph.set_link_policy(LinkPolicy::SINGLE_INPUT | LinkPolicy::ACYCLIC);
+let node = graph.add_node("Add");
+let _ = graph.add_input(node);
+let _ = graph.add_output(node);
+
+// every frame
+let (_outer_scope, events) = graph.show(&mut editor_context, &mut |node: &mut GraphNode<&str>, mut scope: NodeScope<'_>| {
+    scope.add_titlebar(|| ui.text(node.data));
+    for &input in node.inputs() {
+        scope.add_input(input, PinShape::Circle, || ui.text("in"));
+    }
+    for &output in node.outputs() {
+        scope.add_output(output, PinShape::Circle, || ui.text("out"));
+    }
+});
+for event in events {
+    if let GraphEvent::LinkRejected(reason) = event {
+        println!("{reason}");
+    }
+}
+```
+*/
+
+use crate::{
+    Edge, EditorContext, EditorEvent, EditorScope, IdentifierGenerator, InputPinId, LinkId, NodeId,
+    NodeScope, OuterScope, OutputPinId, PinId, PinKind, Topology, editor,
+};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Draws the content of the nodes of a [`Graph`].
+///
+/// Implemented for closures taking the same arguments as [`NodeRenderer::render`].
+pub trait NodeRenderer<N> {
+    /// Draws `node` into `scope`.
+    ///
+    /// Add every pin of [`GraphNode::inputs`] and [`GraphNode::outputs`], otherwise the links
+    /// attached to the missing pins cannot be drawn.
+    fn render(&mut self, node: &mut GraphNode<N>, scope: NodeScope<'_>);
+}
+
+impl<N, F> NodeRenderer<N> for F
+where
+    F: FnMut(&mut GraphNode<N>, NodeScope<'_>),
+{
+    fn render(&mut self, node: &mut GraphNode<N>, scope: NodeScope<'_>) {
+        self(node, scope);
+    }
+}
+
+bitflags::bitflags! {
+    /// Rules every new link of a [`Graph`] has to follow, none by default.
+    ///
+    /// Set with [`Graph::set_link_policy`]. Rules are combined with `|`.
+    #[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+    pub struct LinkPolicy: u32 {
+        /// An input accepts at most one link. A new link replaces the one already ending at the
+        /// input.
+        const SINGLE_INPUT = 1;
+        /// Links from a node to itself are rejected.
+        const NO_SELF_LOOPS = 1 << 1;
+        /// Links which would close a cycle are rejected, including links from a node to itself.
+        const ACYCLIC = 1 << 2;
+    }
+}
+
+/// Reason why a [`Graph`] did not add a link.
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub enum LinkRejection {
+    /// The pin does not belong to the graph.
+    UnknownPin(PinId),
+    /// The start of the link is not an output pin or its end is not an input pin.
+    WrongPinKind {
+        /// The pin of the wrong kind.
+        pin: PinId,
+        /// The kind the pin was added as.
+        kind: PinKind,
+    },
+    /// The link would connect the node to itself, see [`LinkPolicy::NO_SELF_LOOPS`].
+    SelfLoop(NodeId),
+    /// The link would close a cycle, as `end_node` already leads to `start_node`.
+    /// See [`LinkPolicy::ACYCLIC`].
+    Cycle {
+        /// The node of the output pin.
+        start_node: NodeId,
+        /// The node of the input pin.
+        end_node: NodeId,
+    },
+}
+
+impl fmt::Display for LinkRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownPin(pin) => write!(f, "{pin:?} does not belong to the graph"),
+            Self::WrongPinKind { pin, kind } => {
+                write!(f, "cannot link {pin:?}, it is a pin of kind {kind:?}")
+            }
+            Self::SelfLoop(node) => write!(f, "cannot link {node:?} to itself"),
+            Self::Cycle {
+                start_node,
+                end_node,
+            } => write!(
+                f,
+                "cannot link {start_node:?} to {end_node:?}, the link would close a cycle"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LinkRejection {}
+
+/// A change [`Graph::apply_events`] made to a graph in response to the user.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GraphEvent {
+    /// The link the user created was added.
+    LinkAdded(LinkId),
+    /// The link was removed, because the user detached it or a new link replaced it.
+    LinkRemoved(LinkId),
+    /// The link the user created was not added.
+    LinkRejected(LinkRejection),
+}
+
+/// A node of a [`Graph`] with its pins.
+#[derive(Debug, Clone)]
+pub struct GraphNode<N> {
+    id: NodeId,
+    inputs: Vec<InputPinId>,
+    outputs: Vec<OutputPinId>,
+    /// The user data of the node.
+    pub data: N,
+}
+
+impl<N> GraphNode<N> {
+    /// Returns the ID of the node.
+    #[must_use]
+    pub fn id(&self) -> NodeId {
+        self.id
+    }
+
+    /// Returns the input pins in the order they were added.
+    #[must_use]
+    pub fn inputs(&self) -> &[InputPinId] {
+        &self.inputs
+    }
+
+    /// Returns the output pins in the order they were added.
+    #[must_use]
+    pub fn outputs(&self) -> &[OutputPinId] {
+        &self.outputs
+    }
+}
+
+/// A link of a [`Graph`] from an output pin to an input pin.
+#[derive(Debug, Clone)]
+pub struct GraphLink<L> {
+    id: LinkId,
+    start_node: NodeId,
+    end_node: NodeId,
+    start_pin: OutputPinId,
+    end_pin: InputPinId,
+    /// The user data of the link.
+    pub data: L,
+}
+
+impl<L> GraphLink<L> {
+    /// Returns the ID of the link.
+    #[must_use]
+    pub fn id(&self) -> LinkId {
+        self.id
+    }
+
+    /// Returns the node of the output pin.
+    #[must_use]
+    pub fn start_node(&self) -> NodeId {
+        self.start_node
+    }
+
+    /// Returns the node of the input pin.
+    #[must_use]
+    pub fn end_node(&self) -> NodeId {
+        self.end_node
+    }
+
+    /// Returns the output pin the link starts at.
+    #[must_use]
+    pub fn start_pin(&self) -> OutputPinId {
+        self.start_pin
+    }
+
+    /// Returns the input pin the link ends at.
+    #[must_use]
+    pub fn end_pin(&self) -> InputPinId {
+        self.end_pin
+    }
+}
+
+impl<L> Edge for GraphLink<L> {
+    fn start_node(&self) -> NodeId {
+        self.start_node
+    }
+
+    fn end_node(&self) -> NodeId {
+        self.end_node
+    }
+}
+
+/// Nodes with user data `N` and links with user data `L`, drawn into an editor.
+///
+/// The graph allocates the IDs of its nodes, pins and links from its own
+/// [`IdentifierGenerator`]. Use [`Graph::identifier_generator`] for other IDs in the same
+/// editor, e.g. static attributes, so they do not collide.
+#[derive(Debug)]
+pub struct Graph<N, L = ()> {
+    id_gen: IdentifierGenerator,
+    nodes: Vec<GraphNode<N>>,
+    links: Vec<GraphLink<L>>,
+    /// The node and kind of every pin.
+    pins: HashMap<PinId, (NodeId, PinKind)>,
+    link_policy: LinkPolicy,
+}
+
+impl<N, L> Default for Graph<N, L> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N, L> Graph<N, L> {
+    /// Creates an empty graph.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            id_gen: IdentifierGenerator::new(),
+            nodes: Vec::new(),
+            links: Vec::new(),
+            pins: HashMap::new(),
+            link_policy: LinkPolicy::empty(),
+        }
+    }
+
+    /// Sets the rules new links have to follow.
+    ///
+    /// Links already in the graph are not checked again.
+    pub fn set_link_policy(&mut self, link_policy: LinkPolicy) -> &mut Self {
+        self.link_policy = link_policy;
+        self
+    }
+
+    /// Returns the rules new links have to follow.
+    #[must_use]
+    pub fn link_policy(&self) -> LinkPolicy {
+        self.link_policy
+    }
+
+    /// Returns the generator the graph allocates its IDs from.
+    pub fn identifier_generator(&mut self) -> &mut IdentifierGenerator {
+        &mut self.id_gen
+    }
+
+    /// Adds a node without pins.
+    pub fn add_node(&mut self, data: N) -> NodeId {
+        let id = self.id_gen.next_node();
+        self.nodes.push(GraphNode {
+            id,
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            data,
+        });
+        id
+    }
+
+    /// Adds an input pin to `node`.
+    ///
+    /// Returns `None` if the graph does not contain `node`.
+    pub fn add_input(&mut self, node: NodeId) -> Option<InputPinId> {
+        let index = self.node_index(node)?;
+        let pin = self.id_gen.next_input_pin();
+        self.nodes[index].inputs.push(pin);
+        self.pins.insert(pin.into(), (node, PinKind::Input));
+        Some(pin)
+    }
+
+    /// Adds an output pin to `node`.
+    ///
+    /// Returns `None` if the graph does not contain `node`.
+    pub fn add_output(&mut self, node: NodeId) -> Option<OutputPinId> {
+        let index = self.node_index(node)?;
+        let pin = self.id_gen.next_output_pin();
+        self.nodes[index].outputs.push(pin);
+        self.pins.insert(pin.into(), (node, PinKind::Output));
+        Some(pin)
+    }
+
+    /// Removes `node` together with its pins and all links attached to them.
+    ///
+    /// Returns the data of the node, or `None` if the graph does not contain it.
+    pub fn remove_node(&mut self, node: NodeId) -> Option<N> {
+        let index = self.node_index(node)?;
+        let removed = self.nodes.remove(index);
+        for &pin in &removed.inputs {
+            self.pins.remove(&pin.into());
+        }
+        for &pin in &removed.outputs {
+            self.pins.remove(&pin.into());
+        }
+        self.links
+            .retain(|link| link.start_node != node && link.end_node != node);
+        Some(removed.data)
+    }
+
+    /// Links the output pin `start` to the input pin `end`, if the [`LinkPolicy`] allows it.
+    ///
+    /// Links from an input or to an output pin are rejected, whatever the policy.
+    /// With [`LinkPolicy::SINGLE_INPUT`], the links already ending at `end` are removed.
+    pub fn add_link(
+        &mut self,
+        start: OutputPinId,
+        end: InputPinId,
+        data: L,
+    ) -> Result<LinkId, LinkRejection> {
+        self.insert_link(start, end, data).map(|(id, _)| id)
+    }
+
+    /// Adds a link like [`Graph::add_link`], returning the IDs of the links it replaced.
+    fn insert_link(
+        &mut self,
+        start: OutputPinId,
+        end: InputPinId,
+        data: L,
+    ) -> Result<(LinkId, Vec<LinkId>), LinkRejection> {
+        let pin = |pin: PinId, expected: PinKind| {
+            let (node, kind) = self.pin(pin).ok_or(LinkRejection::UnknownPin(pin))?;
+            if kind == expected {
+                Ok(node)
+            } else {
+                Err(LinkRejection::WrongPinKind { pin, kind })
+            }
+        };
+        let start_node = pin(start.into(), PinKind::Output)?;
+        let end_node = pin(end.into(), PinKind::Input)?;
+
+        let policy = self.link_policy;
+        if start_node == end_node
+            && policy.intersects(LinkPolicy::NO_SELF_LOOPS | LinkPolicy::ACYCLIC)
+        {
+            return Err(LinkRejection::SelfLoop(start_node));
+        }
+        let replaced: Vec<LinkId> = if policy.contains(LinkPolicy::SINGLE_INPUT) {
+            self.links_to(end).map(|link| link.id).collect()
+        } else {
+            Vec::new()
+        };
+        if policy.contains(LinkPolicy::ACYCLIC)
+            && self
+                .topology_without(&replaced)
+                .downstream(end_node)
+                .contains(&start_node)
+        {
+            return Err(LinkRejection::Cycle {
+                start_node,
+                end_node,
+            });
+        }
+
+        self.links.retain(|link| !replaced.contains(&link.id));
+        let id = self.id_gen.next_link();
+        self.links.push(GraphLink {
+            id,
+            start_node,
+            end_node,
+            start_pin: start,
+            end_pin: end,
+            data,
+        });
+        Ok((id, replaced))
+    }
+
+    /// Returns the topology of the graph, e.g. to evaluate its nodes in topological order.
+    #[must_use]
+    pub fn topology(&self) -> Topology {
+        self.topology_without(&[])
+    }
+
+    /// Returns the topology of the graph without the links `except`.
+    fn topology_without(&self, except: &[LinkId]) -> Topology {
+        Topology::new(
+            self.nodes.iter().map(GraphNode::id),
+            self.links.iter().filter(|link| !except.contains(&link.id)),
+        )
+    }
+
+    /// Removes `link`, returning its data if the graph contained it.
+    pub fn remove_link(&mut self, link: LinkId) -> Option<L> {
+        let index = self.links.iter().position(|l| l.id == link)?;
+        Some(self.links.remove(index).data)
+    }
+
+    fn node_index(&self, node: NodeId) -> Option<usize> {
+        self.nodes.iter().position(|n| n.id == node)
+    }
+
+    /// Returns the node with the given ID.
+    #[must_use]
+    pub fn node(&self, node: NodeId) -> Option<&GraphNode<N>> {
+        self.nodes.iter().find(|n| n.id == node)
+    }
+
+    /// Returns the node with the given ID mutably.
+    #[must_use]
+    pub fn node_mut(&mut self, node: NodeId) -> Option<&mut GraphNode<N>> {
+        self.nodes.iter_mut().find(|n| n.id == node)
+    }
+
+    /// Returns the link with the given ID.
+    #[must_use]
+    pub fn link(&self, link: LinkId) -> Option<&GraphLink<L>> {
+        self.links.iter().find(|l| l.id == link)
+    }
+
+    /// Returns the link with the given ID mutably.
+    #[must_use]
+    pub fn link_mut(&mut self, link: LinkId) -> Option<&mut GraphLink<L>> {
+        self.links.iter_mut().find(|l| l.id == link)
+    }
+
+    /// Returns all nodes in the order they were added.
+    #[must_use]
+    pub fn nodes(&self) -> &[GraphNode<N>] {
+        &self.nodes
+    }
+
+    /// Returns all nodes mutably.
+    #[must_use]
+    pub fn nodes_mut(&mut self) -> &mut [GraphNode<N>] {
+        &mut self.nodes
+    }
+
+    /// Returns all links in the order they were added.
+    #[must_use]
+    pub fn links(&self) -> &[GraphLink<L>] {
+        &self.links
+    }
+
+    /// Returns the node a pin belongs to and whether it is an input or an output.
+    #[must_use]
+    pub fn pin(&self, pin: impl Into<PinId>) -> Option<(NodeId, PinKind)> {
+        self.pins.get(&pin.into()).copied()
+    }
+
+    /// Returns the links ending at the input pin `pin`.
+    pub fn links_to(&self, pin: InputPinId) -> impl Iterator<Item = &GraphLink<L>> {
+        self.links.iter().filter(move |link| link.end_pin == pin)
+    }
+
+    /// Returns the links starting at the output pin `pin`.
+    pub fn links_from(&self, pin: OutputPinId) -> impl Iterator<Item = &GraphLink<L>> {
+        self.links.iter().filter(move |link| link.start_pin == pin)
+    }
+
+    /// Adds all nodes through `renderer` and all links to the editor.
+    pub fn render<R>(&mut self, editor_scope: &mut EditorScope<'_, '_>, renderer: &mut R)
+    where
+        R: NodeRenderer<N> + ?Sized,
+    {
+        for node in &mut self.nodes {
+            editor_scope.add_node(node.id, |scope| renderer.render(node, scope));
+        }
+        for link in &self.links {
+            editor_scope.add_link(link.id, link.end_pin, link.start_pin);
+        }
+    }
+
+    /// Applies the links the user created or detached in the editor and returns the changes.
+    ///
+    /// Created links get `L::default()` as data and are checked against the [`LinkPolicy`],
+    /// rejected links are reported as [`GraphEvent::LinkRejected`], e.g. to show the reason
+    /// to the user.
+    #[must_use]
+    pub fn apply_events(&mut self, outer_scope: &OuterScope) -> Vec<GraphEvent>
+    where
+        L: Default,
+    {
+        let mut events = Vec::new();
+        for event in outer_scope.events() {
+            match event {
+                EditorEvent::LinkCreated(link) => {
+                    match self.insert_link(link.start_pin, link.end_pin, L::default()) {
+                        Ok((id, replaced)) => {
+                            events.extend(replaced.into_iter().map(GraphEvent::LinkRemoved));
+                            events.push(GraphEvent::LinkAdded(id));
+                        }
+                        Err(rejection) => events.push(GraphEvent::LinkRejected(rejection)),
+                    }
+                }
+                EditorEvent::LinkDestroyed(link) if self.remove_link(*link).is_some() => {
+                    events.push(GraphEvent::LinkRemoved(*link));
+                }
+                _ => {}
+            }
+        }
+        events
+    }
+
+    /// Draws the graph as the whole content of the editor and applies the user's changes.
+    ///
+    /// Use [`Graph::render`] inside [`editor`] and [`Graph::apply_events`] to add more content.
+    pub fn show<R>(
+        &mut self,
+        context: &mut EditorContext,
+        renderer: &mut R,
+    ) -> (OuterScope, Vec<GraphEvent>)
+    where
+        L: Default,
+        R: NodeRenderer<N> + ?Sized,
+    {
+        let outer_scope = editor(context, |mut editor_scope| {
+            self.render(&mut editor_scope, renderer);
+        });
+        let events = self.apply_events(&outer_scope);
+        (outer_scope, events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Link;
+
+    /// Adds `count` nodes with one input and one output each.
+    fn graph(count: usize, policy: LinkPolicy) -> (Graph<()>, Vec<(InputPinId, OutputPinId)>) {
+        let mut graph = Graph::new();
+        let _ = graph.set_link_policy(policy);
+        let pins = (0..count)
+            .map(|_| {
+                let node = graph.add_node(());
+                (
+                    graph.add_input(node).unwrap(),
+                    graph.add_output(node).unwrap(),
+                )
+            })
+            .collect();
+        (graph, pins)
+    }
+
+    fn created(graph: &Graph<()>, start: OutputPinId, end: InputPinId) -> EditorEvent {
+        EditorEvent::LinkCreated(Link {
+            start_node: graph.pin(start).unwrap().0,
+            end_node: graph.pin(end).unwrap().0,
+            start_pin: start,
+            end_pin: end,
+            created_from_snap: false,
+        })
+    }
+
+    #[test]
+    fn allows_anything_without_policy() {
+        let (mut graph, pins) = graph(2, LinkPolicy::empty());
+        let first = graph.add_link(pins[0].1, pins[1].0, ()).unwrap();
+        let second = graph.add_link(pins[0].1, pins[1].0, ()).unwrap();
+        let _ = graph.add_link(pins[1].1, pins[0].0, ()).unwrap();
+        let _ = graph.add_link(pins[0].1, pins[0].0, ()).unwrap();
+        assert_eq!(graph.links().len(), 4);
+        assert_eq!(
+            graph
+                .links_to(pins[1].0)
+                .map(GraphLink::id)
+                .collect::<Vec<_>>(),
+            vec![first, second]
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_pins() {
+        let (mut graph, pins) = graph(1, LinkPolicy::empty());
+        // Pins of another graph with the same IDs as pins of this one would be accepted.
+        let (_, other_pins) = self::graph(2, LinkPolicy::empty());
+        let unknown = other_pins[1].0;
+        assert_eq!(
+            graph.add_link(pins[0].1, unknown, ()),
+            Err(LinkRejection::UnknownPin(unknown.into()))
+        );
+    }
+
+    #[test]
+    fn single_input_replaces_links() {
+        let (mut graph, pins) = graph(3, LinkPolicy::SINGLE_INPUT);
+        let first = graph.add_link(pins[0].1, pins[2].0, ()).unwrap();
+        let (second, replaced) = graph.insert_link(pins[1].1, pins[2].0, ()).unwrap();
+        assert_eq!(replaced, vec![first]);
+        assert!(graph.link(first).is_none());
+        assert_eq!(
+            graph.link(second).unwrap().start_node(),
+            graph.nodes()[1].id()
+        );
+        // Outputs may still have several links.
+        let _ = graph.add_link(pins[1].1, pins[0].0, ()).unwrap();
+        assert_eq!(graph.links_from(pins[1].1).count(), 2);
+    }
+
+    #[test]
+    fn rejects_links_between_pins_of_the_same_kind() {
+        let (mut graph, pins) = graph(2, LinkPolicy::SINGLE_INPUT);
+        let [(first_input, first_output), (second_input, second_output)] = pins[..] else {
+            unreachable!()
+        };
+        let input_as_output = OutputPinId {
+            id: PinId::from(first_input).id,
+        };
+        let output_as_input = InputPinId {
+            id: PinId::from(second_output).id,
+        };
+
+        assert_eq!(
+            graph.add_link(input_as_output, second_input, ()),
+            Err(LinkRejection::WrongPinKind {
+                pin: first_input.into(),
+                kind: PinKind::Input,
+            })
+        );
+        assert_eq!(
+            graph.add_link(first_output, output_as_input, ()),
+            Err(LinkRejection::WrongPinKind {
+                pin: second_output.into(),
+                kind: PinKind::Output,
+            })
+        );
+        assert!(graph.links().is_empty());
+    }
+
+    #[test]
+    fn rejects_self_loops() {
+        for policy in [LinkPolicy::NO_SELF_LOOPS, LinkPolicy::ACYCLIC] {
+            let (mut graph, pins) = graph(2, policy);
+            let node = graph.nodes()[0].id();
+            assert_eq!(
+                graph.add_link(pins[0].1, pins[0].0, ()),
+                Err(LinkRejection::SelfLoop(node))
+            );
+            assert!(graph.links().is_empty());
+        }
+        // Longer cycles are only rejected by `ACYCLIC`.
+        let (mut graph, pins) = graph(2, LinkPolicy::NO_SELF_LOOPS);
+        let _ = graph.add_link(pins[0].1, pins[1].0, ()).unwrap();
+        let _ = graph.add_link(pins[1].1, pins[0].0, ()).unwrap();
+    }
+
+    #[test]
+    fn acyclic_rejects_cycles() {
+        let (mut graph, pins) = graph(3, LinkPolicy::ACYCLIC);
+        let ids: Vec<NodeId> = graph.nodes().iter().map(GraphNode::id).collect();
+        let _ = graph.add_link(pins[0].1, pins[1].0, ()).unwrap();
+        let _ = graph.add_link(pins[1].1, pins[2].0, ()).unwrap();
+        assert_eq!(
+            graph.add_link(pins[2].1, pins[0].0, ()),
+            Err(LinkRejection::Cycle {
+                start_node: ids[2],
+                end_node: ids[0],
+            })
+        );
+        assert_eq!(graph.links().len(), 2);
+        // Parallel paths are no cycle.
+        let _ = graph.add_link(pins[0].1, pins[2].0, ()).unwrap();
+        assert!(graph.topology().topological_order().is_ok());
+    }
+
+    #[test]
+    fn acyclic_checks_the_links_left_after_replacing() {
+        // A cycle created before the policy was set.
+        let (mut graph, pins) = graph(3, LinkPolicy::empty());
+        let ids: Vec<NodeId> = graph.nodes().iter().map(GraphNode::id).collect();
+        let _ = graph.add_link(pins[0].1, pins[1].0, ()).unwrap();
+        let closing = graph.add_link(pins[1].1, pins[0].0, ()).unwrap();
+        let _ = graph.set_link_policy(LinkPolicy::SINGLE_INPUT | LinkPolicy::ACYCLIC);
+
+        // Replacing the closing link by a link from outside the cycle breaks it.
+        let (_, replaced) = graph.insert_link(pins[2].1, pins[0].0, ()).unwrap();
+        assert_eq!(replaced, vec![closing]);
+        assert!(graph.topology().topological_order().is_ok());
+
+        // The link it replaces is left out of the check, 0 -> 1 still closes the cycle.
+        assert_eq!(
+            graph.insert_link(pins[1].1, pins[0].0, ()),
+            Err(LinkRejection::Cycle {
+                start_node: ids[1],
+                end_node: ids[0],
+            })
+        );
+        assert_eq!(graph.links_to(pins[0].0).count(), 1);
+    }
+
+    #[test]
+    fn applies_editor_events() {
+        let policy = LinkPolicy::SINGLE_INPUT | LinkPolicy::ACYCLIC;
+        let (mut graph, pins) = graph(3, policy);
+        let ids: Vec<NodeId> = graph.nodes().iter().map(GraphNode::id).collect();
+        let existing = graph.add_link(pins[0].1, pins[2].0, ()).unwrap();
+
+        let outer_scope = OuterScope::with_events(vec![
+            created(&graph, pins[0].1, pins[1].0),
+            created(&graph, pins[1].1, pins[2].0),
+            created(&graph, pins[2].1, pins[0].0),
+            created(&graph, pins[1].1, pins[1].0),
+        ]);
+        let events = graph.apply_events(&outer_scope);
+        let added: Vec<LinkId> = graph.links().iter().map(GraphLink::id).collect();
+        assert_eq!(
+            events,
+            vec![
+                GraphEvent::LinkAdded(added[0]),
+                GraphEvent::LinkRemoved(existing),
+                GraphEvent::LinkAdded(added[1]),
+                GraphEvent::LinkRejected(LinkRejection::Cycle {
+                    start_node: ids[2],
+                    end_node: ids[0],
+                }),
+                GraphEvent::LinkRejected(LinkRejection::SelfLoop(ids[1])),
+            ]
+        );
+
+        let outer_scope = OuterScope::with_events(vec![
+            EditorEvent::LinkDestroyed(added[0]),
+            EditorEvent::LinkDestroyed(existing),
+        ]);
+        assert_eq!(
+            graph.apply_events(&outer_scope),
+            vec![GraphEvent::LinkRemoved(added[0])]
+        );
+        assert_eq!(graph.links().len(), 1);
+    }
+}
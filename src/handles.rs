@@ -0,0 +1,206 @@
+//! Handles binding node and link identifiers to the editor they belong to.
+#![allow(deprecated)] // The handles wrap the deprecated free-floating `NodeId`/`LinkId` methods.
+
+use crate::{CoordinateSystem, EditorContext, ImVec2, LinkId, NodeId};
+
+/// A [`NodeId`] bound to the [`EditorContext`] containing the node.
+///
+/// Every method makes the editor current before calling into imnodes, so operations
+/// never end up in another editor by accident. An editor being drawn by [`crate::editor()`]
+/// is made current again afterwards.
+/// Obtained from [`EditorContext::node`] or [`crate::EditorScope::node`].
+#[derive(Debug, Copy, Clone)]
+pub struct NodeHandle<'a> {
+    editor: &'a EditorContext,
+    id: NodeId,
+}
+
+impl<'a> NodeHandle<'a> {
+    pub(crate) fn new(editor: &'a EditorContext, id: NodeId) -> Self {
+        Self { editor, id }
+    }
+
+    /// Returns the ID of the node.
+    #[must_use]
+    pub fn id(&self) -> NodeId {
+        self.id
+    }
+
+    /// Runs `f` on the ID with the node's editor current.
+    fn current<R>(&self, f: impl FnOnce(NodeId) -> R) -> R {
+        self.editor.with_current_editor(|| f(self.id))
+    }
+
+    /// Sets whether this node can be dragged by the user.
+    #[doc(alias = "SetNodeDraggable")]
+    #[must_use]
+    pub fn set_draggable(&self, draggable: bool) -> &Self {
+        self.current(|id| {
+            let _ = id.set_draggable(draggable);
+        });
+        self
+    }
+
+    /// Pans the editor view to center on this node.
+    #[doc(alias = "EditorContextMoveToNode")]
+    #[must_use]
+    pub fn move_editor_to(&self) -> &Self {
+        self.current(|id| {
+            let _ = id.move_editor_to();
+        });
+        self
+    }
+
+    /// Gets the dimensions (width, height) of this node.
+    ///
+    /// Note: This must be called *after* the node has been submitted in the current frame,
+    /// otherwise the dimensions might be outdated or zero.
+    #[doc(alias = "GetNodeDimensions")]
+    #[must_use]
+    pub fn get_dimensions(&self) -> ImVec2 {
+        self.current(|id| id.get_dimensions())
+    }
+
+    /// Sets the position of the top-left corner of this node in the specified coordinate system.
+    #[doc(
+        alias = "SetNodeScreenSpacePos",
+        alias = "SetNodeEditorSpacePos",
+        alias = "SetNodeGridSpacePos"
+    )]
+    #[must_use]
+    pub fn set_position(&self, x: f32, y: f32, coordinate_system: CoordinateSystem) -> &Self {
+        self.current(|id| {
+            let _ = id.set_position(x, y, coordinate_system);
+        });
+        self
+    }
+
+    /// Gets the position of the top-left corner of this node in the specified coordinate system.
+    ///
+    /// Note: This must be called *after* the node has been submitted in the current frame,
+    /// otherwise the position might be outdated.
+    #[doc(
+        alias = "GetNodeScreenSpacePos",
+        alias = "GetNodeEditorSpacePos",
+        alias = "GetNodeGridSpacePos"
+    )]
+    #[must_use]
+    pub fn get_position(&self, coordinate_system: CoordinateSystem) -> ImVec2 {
+        self.current(|id| id.get_position(coordinate_system))
+    }
+
+    /// Aligns the node's top-left corner to the grid lines.
+    /// Requires the [`crate::StyleFlags::GRID_SNAPPING`] flag to be enabled in the style.
+    #[doc(alias = "SnapNodeToGrid")]
+    #[must_use]
+    pub fn snap_to_grid(&self) -> &Self {
+        self.current(|id| {
+            let _ = id.snap_to_grid();
+        });
+        self
+    }
+
+    /// Selects this node.
+    #[doc(alias = "SelectNode")]
+    #[must_use]
+    pub fn select(&self) -> &Self {
+        self.current(|id| {
+            let _ = id.select();
+        });
+        self
+    }
+
+    /// Deselects this node.
+    #[doc(alias = "ClearNodeSelection_Int")]
+    #[must_use]
+    pub fn deselect(&self) -> &Self {
+        self.current(|id| {
+            let _ = id.deselect();
+        });
+        self
+    }
+
+    /// Checks if this node is currently selected.
+    #[doc(alias = "IsNodeSelected")]
+    #[must_use]
+    pub fn is_selected(&self) -> bool {
+        self.current(|id| id.is_selected())
+    }
+}
+
+/// A [`LinkId`] bound to the [`EditorContext`] containing the link.
+///
+/// Every method makes the editor current before calling into imnodes.
+/// Obtained from [`EditorContext::link`] or [`crate::EditorScope::link`].
+#[derive(Debug, Copy, Clone)]
+pub struct LinkHandle<'a> {
+    editor: &'a EditorContext,
+    id: LinkId,
+}
+
+impl<'a> LinkHandle<'a> {
+    pub(crate) fn new(editor: &'a EditorContext, id: LinkId) -> Self {
+        Self { editor, id }
+    }
+
+    /// Returns the ID of the link.
+    #[must_use]
+    pub fn id(&self) -> LinkId {
+        self.id
+    }
+
+    /// Runs `f` on the ID with the link's editor current.
+    fn current<R>(&self, f: impl FnOnce(LinkId) -> R) -> R {
+        self.editor.with_current_editor(|| f(self.id))
+    }
+
+    /// Selects this link.
+    #[doc(alias = "SelectLink")]
+    #[must_use]
+    pub fn select(&self) -> &Self {
+        self.current(|id| {
+            let _ = id.select();
+        });
+        self
+    }
+
+    /// Deselects this link.
+    #[doc(alias = "ClearLinkSelection_Int")]
+    #[must_use]
+    pub fn deselect(&self) -> &Self {
+        self.current(|id| {
+            let _ = id.deselect();
+        });
+        self
+    }
+
+    /// Checks if this link is currently selected.
+    #[doc(alias = "IsLinkSelected")]
+    #[must_use]
+    pub fn is_selected(&self) -> bool {
+        self.current(|id| id.is_selected())
+    }
+}
+
+impl EditorContext {
+    /// Returns a handle to operate on the node with the given ID in this editor.
+    ///
+    /// ```no_run
+    /// # let editor: imnodes::EditorContext = unimplemented!();
+    /// # let id: imnodes::NodeId = unimplemented!();
+    /// let _ = editor
+    ///     .node(id)
+    ///     .set_position(10.0, 10.0, imnodes::CoordinateSystem::GridSpace)
+    ///     .select();
+    /// ```
+    #[must_use]
+    pub fn node(&self, id: NodeId) -> NodeHandle<'_> {
+        NodeHandle::new(self, id)
+    }
+
+    /// Returns a handle to operate on the link with the given ID in this editor.
+    #[must_use]
+    pub fn link(&self, id: LinkId) -> LinkHandle<'_> {
+        LinkHandle::new(self, id)
+    }
+}
@@ -9,37 +9,48 @@ impl EditorContext {
     pub fn get_panning(&self) -> sys::ImVec2 {
         let mut position = sys::ImVec2 { x: 0.0, y: 0.0 };
         // Safety: C API call. `position` is written to by the function.
-        // Assumes the current context is set correctly before calling editor functions.
-        unsafe {
+        self.with_current_editor(|| unsafe {
             sys::imnodes_EditorContextGetPanning(core::ptr::from_mut(&mut position));
-        }
+        });
         position
     }
 
+    /// Converts a position in screen space into grid space.
+    ///
+    /// Uses the canvas position of the last [`crate::editor()`] call for this editor
+    /// and its current panning. See [`crate::CoordinateSystem`].
+    #[must_use]
+    pub fn screen_to_grid_space(&self, pos: [f32; 2]) -> [f32; 2] {
+        let panning = self.get_panning();
+        [
+            pos[0] - self.canvas_origin[0] - panning.x,
+            pos[1] - self.canvas_origin[1] - panning.y,
+        ]
+    }
+
     /// Sets the panning offset of the editor canvas.
     #[doc(alias = "EditorContextResetPanning")]
     pub fn reset_panning(&self, pos: sys::ImVec2) {
         // Safety: C API call. Assumes `pos` is valid.
-        // Assumes the current context is set correctly.
-        unsafe { sys::imnodes_EditorContextResetPanning(pos) };
+        self.with_current_editor(|| unsafe { sys::imnodes_EditorContextResetPanning(pos) });
     }
 
     /// Clears the current selection of nodes.
-    /// If a specific node ID is provided via [`crate::NodeId::deselect`], only that node is deselected.
+    /// To deselect a single node, use [`crate::NodeHandle::deselect`].
     #[doc(alias = "ClearNodeSelection")]
     pub fn clear_node_selection(&self) {
         // Calls the _Nil version which clears all node selections.
-        // Safety: C API call. Assumes the current context is set correctly.
-        unsafe { sys::imnodes_ClearNodeSelection_Nil() };
+        // Safety: C API call.
+        self.with_current_editor(|| unsafe { sys::imnodes_ClearNodeSelection_Nil() });
     }
 
     /// Clears the current selection of links.
-    /// If a specific link ID is provided via [`crate::LinkId::deselect`], only that link is deselected.
+    /// To deselect a single link, use [`crate::LinkHandle::deselect`].
     #[doc(alias = "ClearLinkSelection")]
     pub fn clear_link_selection(&self) {
         // Calls the _Nil version which clears all link selections.
-        // Safety: C API call. Assumes the current context is set correctly.
-        unsafe { sys::imnodes_ClearLinkSelection_Nil() };
+        // Safety: C API call.
+        self.with_current_editor(|| unsafe { sys::imnodes_ClearLinkSelection_Nil() });
     }
 }
 
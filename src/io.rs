@@ -0,0 +1,229 @@
+/*!
+Safe access to the input settings of an imnodes [`Context`].
+
+The modifier settings of `ImNodesIO` are pointers to the modifier key state in imgui's IO.
+[`Io`] points them at the fields of the imgui context the imnodes context was created against,
+so they stay valid for the lifetime of the context.
+*/
+
+use crate::{Context, EditorContext, ImNodesIO};
+use std::marker::PhantomData;
+
+/// A modifier key which can enable an imnodes interaction.
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum Modifier {
+    /// The Ctrl key, `ImGuiIO::KeyCtrl`.
+    Ctrl,
+    /// The Shift key, `ImGuiIO::KeyShift`.
+    Shift,
+    /// The Alt key, `ImGuiIO::KeyAlt`.
+    Alt,
+    /// The Cmd/Super/Windows key, `ImGuiIO::KeySuper`.
+    Super,
+}
+
+impl Modifier {
+    /// All modifiers.
+    pub const ALL: [Self; 4] = [Self::Ctrl, Self::Shift, Self::Alt, Self::Super];
+
+    fn key_state(self, io: *const imgui::sys::ImGuiIO) -> *const bool {
+        // Safety: Only computes the field address, `io` points to a live `ImGuiIO`.
+        unsafe {
+            match self {
+                Self::Ctrl => &raw const (*io).KeyCtrl,
+                Self::Shift => &raw const (*io).KeyShift,
+                Self::Alt => &raw const (*io).KeyAlt,
+                Self::Super => &raw const (*io).KeySuper,
+            }
+        }
+    }
+}
+
+/// The input settings of an imnodes [`Context`], shared by all its editors.
+///
+/// Obtained from [`Context::io`] or [`EditorContext::io`].
+///
+/// ```no_run
+/// # let mut context: imnodes::Context = unimplemented!();
+/// let _ = context
+///     .io()
+///     .set_multiple_select_modifier(imnodes::Modifier::Ctrl)
+///     .set_link_detach_with_modifier_click(imnodes::Modifier::Alt)
+///     .set_alt_mouse_button(imgui::MouseButton::Middle);
+/// ```
+#[derive(Debug)]
+pub struct Io<'a> {
+    raw: *mut ImNodesIO,
+    imgui: *const imgui::sys::ImGuiIO,
+    // The settings live inside the context.
+    _context: PhantomData<&'a mut ImNodesIO>,
+}
+
+impl Io<'_> {
+    /// `raw` and `imgui` must stay valid for the lifetime of the returned value.
+    pub(crate) fn new(raw: *mut ImNodesIO, imgui: *const imgui::sys::ImGuiIO) -> Self {
+        Self {
+            raw,
+            imgui,
+            _context: PhantomData,
+        }
+    }
+
+    fn raw(&self) -> &ImNodesIO {
+        // Safety: `raw` is valid for the lifetime of `self`.
+        unsafe { &*self.raw }
+    }
+
+    fn raw_mut(&mut self) -> &mut ImNodesIO {
+        // Safety: `raw` is valid for the lifetime of `self`, which borrows the context mutably.
+        unsafe { &mut *self.raw }
+    }
+
+    fn modifier_pointer(&self, modifier: impl Into<Option<Modifier>>) -> *const bool {
+        modifier
+            .into()
+            .map_or(std::ptr::null(), |modifier| modifier.key_state(self.imgui))
+    }
+
+    fn modifier_of(&self, pointer: *const bool) -> Option<Modifier> {
+        Modifier::ALL
+            .into_iter()
+            .find(|modifier| modifier.key_state(self.imgui) == pointer)
+    }
+
+    /// Sets the modifier which, held together with the left mouse button, emulates the middle
+    /// mouse button to pan the editor. `None` disables the emulation.
+    #[doc(alias = "EmulateThreeButtonMouse")]
+    #[must_use]
+    pub fn set_emulate_three_button_mouse(
+        &mut self,
+        modifier: impl Into<Option<Modifier>>,
+    ) -> &mut Self {
+        let pointer = self.modifier_pointer(modifier);
+        self.raw_mut().EmulateThreeButtonMouse.Modifier = pointer;
+        self
+    }
+
+    /// Returns the modifier emulating the middle mouse button, if any.
+    #[doc(alias = "EmulateThreeButtonMouse")]
+    #[must_use]
+    pub fn emulate_three_button_mouse(&self) -> Option<Modifier> {
+        self.modifier_of(self.raw().EmulateThreeButtonMouse.Modifier)
+    }
+
+    /// Sets the modifier which has to be held when clicking a pin to detach its links.
+    /// `None` detaches links by just dragging them away from the pin.
+    #[doc(alias = "LinkDetachWithModifierClick")]
+    #[must_use]
+    pub fn set_link_detach_with_modifier_click(
+        &mut self,
+        modifier: impl Into<Option<Modifier>>,
+    ) -> &mut Self {
+        let pointer = self.modifier_pointer(modifier);
+        self.raw_mut().LinkDetachWithModifierClick.Modifier = pointer;
+        self
+    }
+
+    /// Returns the modifier needed to detach links, if any.
+    #[doc(alias = "LinkDetachWithModifierClick")]
+    #[must_use]
+    pub fn link_detach_with_modifier_click(&self) -> Option<Modifier> {
+        self.modifier_of(self.raw().LinkDetachWithModifierClick.Modifier)
+    }
+
+    /// Sets the modifier which adds clicked nodes and links to the selection.
+    /// `None` restores the default, which is Ctrl.
+    #[doc(alias = "MultipleSelectModifier")]
+    #[must_use]
+    pub fn set_multiple_select_modifier(
+        &mut self,
+        modifier: impl Into<Option<Modifier>>,
+    ) -> &mut Self {
+        let pointer = self.modifier_pointer(modifier);
+        self.raw_mut().MultipleSelectModifier.Modifier = pointer;
+        self
+    }
+
+    /// Returns the modifier which adds to the selection.
+    ///
+    /// Returns `None` for the default, which imnodes resolves to Ctrl.
+    #[doc(alias = "MultipleSelectModifier")]
+    #[must_use]
+    pub fn multiple_select_modifier(&self) -> Option<Modifier> {
+        self.modifier_of(self.raw().MultipleSelectModifier.Modifier)
+    }
+
+    /// Sets the mouse button used to pan the editor, the middle button by default.
+    #[doc(alias = "AltMouseButton")]
+    #[must_use]
+    pub fn set_alt_mouse_button(&mut self, button: imgui::MouseButton) -> &mut Self {
+        self.raw_mut().AltMouseButton = button as i32;
+        self
+    }
+
+    /// Returns the mouse button used to pan the editor.
+    #[doc(alias = "AltMouseButton")]
+    #[must_use]
+    pub fn alt_mouse_button(&self) -> imgui::MouseButton {
+        usize::try_from(self.raw().AltMouseButton)
+            .ok()
+            .and_then(|index| imgui::MouseButton::VARIANTS.get(index).copied())
+            .unwrap_or(imgui::MouseButton::Middle)
+    }
+
+    /// Sets how fast the editor pans while dragging a link or a box selection near its border.
+    #[doc(alias = "AutoPanningSpeed")]
+    #[must_use]
+    pub fn set_auto_panning_speed(&mut self, speed: f32) -> &mut Self {
+        self.raw_mut().AutoPanningSpeed = speed;
+        self
+    }
+
+    /// Returns how fast the editor pans near its border.
+    #[doc(alias = "AutoPanningSpeed")]
+    #[must_use]
+    pub fn auto_panning_speed(&self) -> f32 {
+        self.raw().AutoPanningSpeed
+    }
+}
+
+impl Context {
+    /// Returns the input settings of this context, shared by all its editors.
+    #[doc(alias = "GetIO")]
+    #[must_use]
+    pub fn io(&mut self) -> Io<'_> {
+        let (raw, imgui) = self.raw_io();
+        Io::new(raw, imgui)
+    }
+}
+
+impl EditorContext {
+    /// Returns the input settings of the [`Context`] this editor was created from.
+    ///
+    /// The settings are shared by all editors of the context, unless an editor has its own
+    /// settings, see [`EditorContext::own_io`].
+    #[doc(alias = "GetIO")]
+    #[must_use]
+    pub fn io(&mut self) -> Io<'_> {
+        let (raw, imgui) = self.raw_io();
+        Io::new(raw, imgui)
+    }
+
+    /// Returns the input settings owned by this editor, which [`crate::editor()`] uses instead
+    /// of the shared ones while drawing this editor.
+    ///
+    /// The editor gets its own settings on the first call, starting as a copy of the shared ones.
+    #[must_use]
+    pub fn own_io(&mut self) -> Io<'_> {
+        let (raw, imgui) = self.raw_io();
+        // Safety: `raw` points to the live IO settings of the context.
+        let io = self.io.get_or_insert_with(|| unsafe { *raw });
+        Io::new(io, imgui)
+    }
+
+    /// Makes this editor use the shared input settings again.
+    pub fn clear_own_io(&mut self) -> &mut Self {
+        self.io = None;
+        self
+    }
+}
@@ -65,12 +65,45 @@ pub mod internal {
 mod context;
 pub use context::*;
 
+mod editor_state;
+pub use editor_state::{EditorState, Error, ParseEditorStateError};
+
+#[cfg(feature = "graph")]
+pub mod evaluation;
+
+mod events;
+pub(crate) use events::EventTracker;
+pub use events::{EditorEvent, SelectionChange};
+
+#[cfg(feature = "graph")]
+pub mod graph;
+
+mod handles;
+pub use handles::*;
+
 mod helpers;
+
+mod io;
+pub use io::{Io, Modifier};
+
+mod pin_type;
+pub(crate) use pin_type::ErasedPinType;
+pub use pin_type::PinType;
+
+mod popups;
+pub use popups::ContextMenuTarget;
+pub(crate) use popups::{PendingContextMenu, PendingLinkDrop};
 // Helpers are exposed directly on EditorContext or as standalone functions where appropriate.
 
 mod styling;
 pub use styling::*;
 
+#[cfg(feature = "serde")]
+mod style_serde;
+
+mod topology;
+pub use topology::{Cycle, Edge, Topology};
+
 mod scopes;
 pub use scopes::*;
 
@@ -177,6 +210,7 @@ pub struct NodeId {
 
 impl NodeId {
     /// Sets whether this node can be dragged by the user.
+    #[deprecated = "Use `EditorContext::node` or `EditorScope::node`, which make the owning editor current first."]
     #[doc(alias = "SetNodeDraggable")]
     #[must_use]
     pub fn set_draggable(&self, draggable: bool) -> &Self {
@@ -186,6 +220,7 @@ impl NodeId {
     }
 
     /// Pans the editor view to center on this node.
+    #[deprecated = "Use `EditorContext::node` or `EditorScope::node`, which make the owning editor current first."]
     #[doc(alias = "EditorContextMoveToNode")]
     #[must_use]
     pub fn move_editor_to(&self) -> &Self {
@@ -201,6 +236,7 @@ impl NodeId {
     ///
     /// Note: This must be called *after* the node has been submitted in the current frame,
     /// otherwise the dimensions might be outdated or zero.
+    #[deprecated = "Use `EditorContext::node` or `EditorScope::node`, which make the owning editor current first."]
     #[doc(alias = "GetNodeDimensions")]
     #[must_use]
     pub fn get_dimensions(&self) -> ImVec2 {
@@ -213,6 +249,7 @@ impl NodeId {
     }
 
     /// Sets the position of the top-left corner of this node in the specified coordinate system.
+    #[deprecated = "Use `EditorContext::node` or `EditorScope::node`, which make the owning editor current first."]
     #[doc(
         alias = "SetNodeScreenSpacePos",
         alias = "SetNodeEditorSpacePos",
@@ -240,6 +277,7 @@ impl NodeId {
     ///
     /// Note: This must be called *after* the node has been submitted in the current frame,
     /// otherwise the position might be outdated.
+    #[deprecated = "Use `EditorContext::node` or `EditorScope::node`, which make the owning editor current first."]
     #[doc(
         alias = "GetNodeScreenSpacePos",
         alias = "GetNodeEditorSpacePos",
@@ -264,7 +302,8 @@ impl NodeId {
     }
 
     /// Aligns the node's top-left corner to the grid lines.
-    /// Requires the [`StyleFlags::GridSnapping`] flag to be enabled in the style.
+    /// Requires the [`StyleFlags::GRID_SNAPPING`] flag to be enabled in the style.
+    #[deprecated = "Use `EditorContext::node` or `EditorScope::node`, which make the owning editor current first."]
     #[doc(alias = "SnapNodeToGrid")]
     #[must_use]
     pub fn snap_to_grid(&self) -> &Self {
@@ -274,6 +313,7 @@ impl NodeId {
     }
 
     /// Selects this node.
+    #[deprecated = "Use `EditorContext::node` or `EditorScope::node`, which make the owning editor current first."]
     #[doc(alias = "SelectNode")]
     #[must_use]
     pub fn select(&self) -> &Self {
@@ -284,6 +324,7 @@ impl NodeId {
 
     /// Deselects this node.
     /// If no other nodes are selected, this is equivalent to [`EditorContext::clear_node_selection`].
+    #[deprecated = "Use `EditorContext::node` or `EditorScope::node`, which make the owning editor current first."]
     #[doc(alias = "ClearNodeSelection_Int")]
     #[must_use]
     pub fn deselect(&self) -> &Self {
@@ -293,6 +334,7 @@ impl NodeId {
     }
 
     /// Checks if this node is currently selected.
+    #[deprecated = "Use `EditorContext::node` or `EditorScope::node`, which make the owning editor current first."]
     #[doc(alias = "IsNodeSelected")]
     #[must_use]
     pub fn is_selected(&self) -> bool {
@@ -339,6 +381,26 @@ impl PinId {
     }
 }
 
+/// Whether a pin was added as an input or an output.
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum PinKind {
+    /// Added with [`NodeScope::add_input`].
+    Input,
+    /// Added with [`NodeScope::add_output`].
+    Output,
+}
+
+impl PinKind {
+    /// Returns the kind a pin needs to be connected to a pin of this kind.
+    #[must_use]
+    pub fn opposite(self) -> Self {
+        match self {
+            Self::Input => Self::Output,
+            Self::Output => Self::Input,
+        }
+    }
+}
+
 /// Identifier for an input pin (rendered on the left side of a node).
 ///
 /// IDs must be unique within the editor context. Generated using [IdentifierGenerator::next_input_pin].
@@ -379,6 +441,57 @@ impl From<OutputPinId> for PinId {
     }
 }
 
+/// Identifier for a pin together with its kind.
+///
+/// Used where a pin may be either an input or an output, e.g. in
+/// [`EditorContext::link_drop_popup`]. Converted from [`InputPinId`] and [`OutputPinId`].
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum AnyPinId {
+    /// An input pin.
+    Input(InputPinId),
+    /// An output pin.
+    Output(OutputPinId),
+}
+
+impl AnyPinId {
+    /// Returns whether the pin is an input or an output.
+    #[must_use]
+    pub fn kind(self) -> PinKind {
+        match self {
+            Self::Input(_) => PinKind::Input,
+            Self::Output(_) => PinKind::Output,
+        }
+    }
+
+    pub(crate) fn new(id: PinId, kind: PinKind) -> Self {
+        match kind {
+            PinKind::Input => Self::Input(InputPinId { id: id.id }),
+            PinKind::Output => Self::Output(OutputPinId { id: id.id }),
+        }
+    }
+}
+
+impl From<InputPinId> for AnyPinId {
+    fn from(val: InputPinId) -> Self {
+        Self::Input(val)
+    }
+}
+
+impl From<OutputPinId> for AnyPinId {
+    fn from(val: OutputPinId) -> Self {
+        Self::Output(val)
+    }
+}
+
+impl From<AnyPinId> for PinId {
+    fn from(val: AnyPinId) -> Self {
+        match val {
+            AnyPinId::Input(pin) => pin.into(),
+            AnyPinId::Output(pin) => pin.into(),
+        }
+    }
+}
+
 /// Identifier for a link between two pins.
 ///
 /// IDs must be unique within the editor context. Generated using [IdentifierGenerator::next_link].
@@ -398,6 +511,7 @@ impl LinkId {
     }
 
     /// Selects this link.
+    #[deprecated = "Use `EditorContext::link` or `EditorScope::link`, which make the owning editor current first."]
     #[doc(alias = "SelectLink")]
     #[must_use]
     pub fn select(&self) -> &Self {
@@ -408,6 +522,7 @@ impl LinkId {
 
     /// Deselects this link.
     /// If no other links are selected, this is equivalent to [`EditorContext::clear_link_selection`].
+    #[deprecated = "Use `EditorContext::link` or `EditorScope::link`, which make the owning editor current first."]
     #[doc(alias = "ClearLinkSelection_Int")]
     #[must_use]
     pub fn deselect(&self) -> &Self {
@@ -417,6 +532,7 @@ impl LinkId {
     }
 
     /// Checks if this link is currently selected.
+    #[deprecated = "Use `EditorContext::link` or `EditorScope::link`, which make the owning editor current first."]
     #[doc(alias = "IsLinkSelected")]
     #[must_use]
     pub fn is_selected(&self) -> bool {
@@ -510,3 +626,47 @@ pub struct Link {
     /// Flag indicating if the link was created by snapping to a pin node when [AttributeFlags::EnableLinkCreationOnSnap] is enabled.
     pub created_from_snap: bool,
 }
+
+/// Reason why a link the user created in the editor was not reported as a [`Link`].
+///
+/// Obtained from [`OuterScope::try_links_created()`].
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub enum LinkError {
+    /// Both pins are inputs or both are outputs.
+    SamePinKind {
+        /// The pin where the user started dragging.
+        start_pin: PinId,
+        /// The pin where the user dropped the link.
+        end_pin: PinId,
+        /// The kind of both pins.
+        kind: PinKind,
+    },
+    /// The [`PinType`]s of the pins cannot be linked.
+    IncompatiblePinTypes {
+        /// The output pin of the link.
+        start_pin: OutputPinId,
+        /// The input pin of the link.
+        end_pin: InputPinId,
+    },
+}
+
+impl std::fmt::Display for LinkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SamePinKind {
+                start_pin,
+                end_pin,
+                kind,
+            } => write!(
+                f,
+                "cannot link {start_pin:?} to {end_pin:?}, both pins are of kind {kind:?}"
+            ),
+            Self::IncompatiblePinTypes { start_pin, end_pin } => write!(
+                f,
+                "cannot link {start_pin:?} to {end_pin:?}, the pin types are incompatible"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LinkError {}
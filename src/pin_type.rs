@@ -0,0 +1,98 @@
+/*!
+Data types carried by pins, restricting which pins the user can link.
+
+Pins added with [`crate::NodeScope::add_typed_input`] and [`crate::NodeScope::add_typed_output`]
+remember their [`PinType`] for the current frame. Links between typed pins whose types are not
+compatible are reported as [`crate::LinkError::IncompatiblePinTypes`] instead of as a new link,
+and while the user drags a link, the typed pins it cannot be connected to are greyed out.
+*/
+
+use std::any::Any;
+use std::fmt::Debug;
+
+/// A user-defined data type of pins, e.g. an enum of the value types of a shader graph.
+///
+/// ```
+/// #[derive(Debug, PartialEq)]
+/// enum ValueType {
+///     Float,
+///     Vector,
+///     Texture,
+/// }
+///
+/// impl imnodes::PinType for ValueType {
+///     fn can_link_to(&self, input: &Self) -> bool {
+///         // floats are promoted to vectors
+///         self == input || (*self == Self::Float && *input == Self::Vector)
+///     }
+/// }
+/// ```
+pub trait PinType: Debug + 'static {
+    /// Returns whether an output of this type can be linked to an input of type `input`.
+    fn can_link_to(&self, input: &Self) -> bool;
+}
+
+/// A [`PinType`] of any type, stored with the pins submitted in a frame.
+pub(crate) trait ErasedPinType: Debug {
+    fn as_any(&self) -> &dyn Any;
+
+    /// Pins of different Rust types are never compatible.
+    fn can_link_to(&self, input: &dyn ErasedPinType) -> bool;
+}
+
+impl<T: PinType> ErasedPinType for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn can_link_to(&self, input: &dyn ErasedPinType) -> bool {
+        input
+            .as_any()
+            .downcast_ref::<T>()
+            .is_some_and(|input| PinType::can_link_to(self, input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    enum Value {
+        Float,
+        Vector,
+    }
+
+    impl PinType for Value {
+        fn can_link_to(&self, input: &Self) -> bool {
+            self == input || (*self == Self::Float && *input == Self::Vector)
+        }
+    }
+
+    #[derive(Debug)]
+    struct Other;
+
+    impl PinType for Other {
+        fn can_link_to(&self, _: &Self) -> bool {
+            true
+        }
+    }
+
+    fn erased(pin_type: &dyn ErasedPinType) -> &dyn ErasedPinType {
+        pin_type
+    }
+
+    #[test]
+    fn uses_the_pin_type_for_the_same_type() {
+        assert!(erased(&Value::Float).can_link_to(&Value::Vector));
+        assert!(erased(&Value::Vector).can_link_to(&Value::Vector));
+        assert!(!erased(&Value::Vector).can_link_to(&Value::Float));
+    }
+
+    #[test]
+    fn rejects_different_types() {
+        assert!(!erased(&Other).can_link_to(&Value::Float));
+        assert!(!erased(&Value::Float).can_link_to(&Other));
+        assert!(erased(&Other).can_link_to(&Other));
+    }
+}
@@ -0,0 +1,198 @@
+/*!
+imgui popups opened in response to interactions with the node editor.
+
+The popups remember what they were opened for in the [`EditorContext`] until they are closed.
+Call the helpers at the same place every frame, so the popup IDs resolve to the same ID stack.
+*/
+
+use crate::{
+    AnyPinId, CoordinateSystem, EditorContext, EditorEvent, EditorScope, Link, LinkError, LinkId,
+    NodeId, OuterScope, PinId, PinKind,
+};
+
+/// What the user right-clicked to open a context menu with [`EditorScope::context_menu`].
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum ContextMenuTarget {
+    /// The empty editor canvas.
+    Canvas,
+    /// A node.
+    Node(NodeId),
+    /// A link.
+    Link(LinkId),
+    /// A pin.
+    Pin(PinId),
+}
+
+/// An open context menu of an [`EditorScope`].
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct PendingContextMenu {
+    target: ContextMenuTarget,
+    grid_pos: [f32; 2],
+}
+
+/// A dropped link waiting for the user to pick a node in [`EditorContext::link_drop_popup`].
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct PendingLinkDrop {
+    from: PinId,
+    node: NodeId,
+    kind: PinKind,
+    grid_pos: [f32; 2],
+}
+
+impl EditorContext {
+    /// Opens an imgui popup where the user dropped a new link on the empty canvas, and connects
+    /// the node created from the popup to the pin the link was dragged from.
+    ///
+    /// `f` builds the popup content every frame while it is open and gets the pin the link was
+    /// dragged from. Once the user picked a node, return its ID and the pin to connect. The node
+    /// is moved to the drop position, the popup is closed and the link between both pins is
+    /// returned so it can be added to the graph.
+    ///
+    /// Returns [`LinkError::SamePinKind`] if the returned pin is of the same kind as the pin the
+    /// link was dragged from, e.g. an input for a link dragged from an input.
+    ///
+    /// Links which were detached from a pin and then dropped are ignored.
+    ///
+    /// ```no_run
+    /// # let ui: &imgui::Ui = unimplemented!();
+    /// # let mut editor_context: imnodes::EditorContext = unimplemented!();
+    /// # let mut id_gen = editor_context.new_identifier_generator();
+    /// let outer_scope = imnodes::editor(&mut editor_context, |_editor_scope| {});
+    /// let new_link = editor_context.link_drop_popup(ui, &outer_scope, "add_node", |from| {
+    ///     if ui.button("Add") {
+    ///         let node = id_gen.next_node();
+    ///         // add the node to your graph, with a pin which can be linked to `from`
+    ///         let pin = match from {
+    ///             imnodes::AnyPinId::Output(_) => id_gen.next_input_pin().into(),
+    ///             imnodes::AnyPinId::Input(_) => id_gen.next_output_pin().into(),
+    ///         };
+    ///         Some((node, pin))
+    ///     } else {
+    ///         None
+    ///     }
+    /// });
+    /// ```
+    pub fn link_drop_popup<F>(
+        &mut self,
+        ui: &imgui::Ui,
+        outer_scope: &OuterScope,
+        popup_id: &str,
+        f: F,
+    ) -> Option<Result<Link, LinkError>>
+    where
+        F: FnOnce(AnyPinId) -> Option<(NodeId, AnyPinId)>,
+    {
+        let dropped = outer_scope.events().iter().find_map(|event| match event {
+            EditorEvent::LinkDropped {
+                from,
+                grid_pos,
+                was_detached: false,
+                ..
+            } => Some((*from, *grid_pos)),
+            _ => None,
+        });
+        if let Some((from, grid_pos)) = dropped
+            && let Some(pin) = self.frame.pins.get(&from)
+        {
+            self.pending_link_drop = Some(PendingLinkDrop {
+                from,
+                node: pin.node,
+                kind: pin.kind,
+                grid_pos,
+            });
+            ui.open_popup(popup_id);
+        }
+
+        let pending = self.pending_link_drop?;
+        let Some(_popup) = ui.begin_popup(popup_id) else {
+            // The popup was closed without picking a node.
+            self.pending_link_drop = None;
+            return None;
+        };
+
+        let (node, pin) = f(AnyPinId::new(pending.from, pending.kind))?;
+        ui.close_current_popup();
+        self.pending_link_drop = None;
+        let _ = self.node(node).set_position(
+            pending.grid_pos[0],
+            pending.grid_pos[1],
+            CoordinateSystem::GridSpace,
+        );
+
+        let link = match (AnyPinId::new(pending.from, pending.kind), pin) {
+            (AnyPinId::Output(start_pin), AnyPinId::Input(end_pin)) => Link {
+                start_node: pending.node,
+                end_node: node,
+                start_pin,
+                end_pin,
+                created_from_snap: false,
+            },
+            (AnyPinId::Input(end_pin), AnyPinId::Output(start_pin)) => Link {
+                start_node: node,
+                end_node: pending.node,
+                start_pin,
+                end_pin,
+                created_from_snap: false,
+            },
+            _ => {
+                return Some(Err(LinkError::SamePinKind {
+                    start_pin: pending.from,
+                    end_pin: pin.into(),
+                    kind: pending.kind,
+                }));
+            }
+        };
+        Some(Ok(link))
+    }
+}
+
+impl EditorScope<'_, '_> {
+    /// Opens a context menu when the user right-clicks into the editor.
+    ///
+    /// The hovered pin, link or node is used as target, falling back to the empty canvas.
+    /// The popup is opened inside the editor canvas, so its ID is scoped to this editor.
+    ///
+    /// `f` builds the menu content every frame while it is open and gets the target and the
+    /// grid space position of the click, e.g. to place a new node there.
+    ///
+    /// ```no_run
+    /// # let ui: &imgui::Ui = unimplemented!();
+    /// # let mut editor_context: imnodes::EditorContext = unimplemented!();
+    /// imnodes::editor(&mut editor_context, |mut editor_scope| {
+    ///     editor_scope.context_menu(ui, "context_menu", |target, grid_pos| match target {
+    ///         imnodes::ContextMenuTarget::Canvas => {
+    ///             if ui.menu_item("Add Node") {
+    ///                 // add a node at `grid_pos`
+    ///             }
+    ///         }
+    ///         imnodes::ContextMenuTarget::Node(_id) => {
+    ///             if ui.menu_item("Delete") {
+    ///                 // remove the node
+    ///             }
+    ///         }
+    ///         _ => {}
+    ///     });
+    /// });
+    /// ```
+    pub fn context_menu<F>(&mut self, ui: &imgui::Ui, popup_id: &str, f: F)
+    where
+        F: FnOnce(ContextMenuTarget, [f32; 2]),
+    {
+        if self.is_hovered() && ui.is_mouse_clicked(imgui::MouseButton::Right) {
+            self.context.pending_context_menu = Some(PendingContextMenu {
+                target: self.context.hovered.unwrap_or(ContextMenuTarget::Canvas),
+                grid_pos: self.context.screen_to_grid_space(ui.io().mouse_pos),
+            });
+            ui.open_popup(popup_id);
+        }
+
+        let Some(pending) = self.context.pending_context_menu else {
+            return;
+        };
+        if let Some(_popup) = ui.begin_popup(popup_id) {
+            f(pending.target, pending.grid_pos);
+        } else {
+            self.context.pending_context_menu = None;
+        }
+    }
+}
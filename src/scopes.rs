@@ -10,15 +10,65 @@ on the parent scope's struct to prevent calling methods from the parent scope wh
 */
 
 use crate::{
-    AttributeId, EditorContext, Hoverable, InputPinId, Link, LinkId, MiniMapLocation, NodeId,
-    OutputPinId, PinId, PinShape, sys,
+    AttributeId, ColorStyle, ContextMenuTarget, EditorContext, EditorEvent, ErasedPinType,
+    Hoverable, InputPinId, Link, LinkError, LinkHandle, LinkId, MiniMapLocation, NodeHandle,
+    NodeId, OutputPinId, PinId, PinKind, PinShape, PinType, SelectionChange, get_hovered_node, sys,
 };
+use imgui::ImColor32;
+use std::collections::HashMap;
+use std::rc::Rc;
 
 /// Represents the scope outside the main node editor block.
 /// Use methods on this struct *after* [`editor()`] has returned to query UI events.
 #[derive(Debug)]
-pub struct OuterScope {}
+pub struct OuterScope {
+    events: Vec<EditorEvent>,
+    created_link: Option<Result<Link, LinkError>>,
+}
 impl OuterScope {
+    /// Creates a scope reporting `events`, for testing code which consumes them.
+    #[cfg(test)]
+    pub(crate) fn with_events(events: Vec<EditorEvent>) -> Self {
+        Self {
+            events,
+            created_link: None,
+        }
+    }
+
+    /// Returns all events of the frame, captured once when the editor ended.
+    ///
+    /// This is a single stream combining [`OuterScope::links_created`],
+    /// [`OuterScope::get_destroyed_link`], [`OuterScope::from_where_link_started`],
+    /// the selection and the hover queries.
+    ///
+    /// ```no_run
+    /// # let mut editor_context: imnodes::EditorContext = unimplemented!();
+    /// let outer_scope = imnodes::editor(&mut editor_context, |_editor_scope| {});
+    /// for event in outer_scope.events() {
+    ///     match event {
+    ///         imnodes::EditorEvent::LinkCreated(link) => println!("new link {link:?}"),
+    ///         imnodes::EditorEvent::LinkDestroyed(id) => println!("removed link {id:?}"),
+    ///         _ => {}
+    ///     }
+    /// }
+    /// ```
+    #[must_use]
+    pub fn events(&self) -> &[EditorEvent] {
+        &self.events
+    }
+
+    /// Returns how the selection changed compared to the previous frame of this editor,
+    /// or `None` if it did not change.
+    ///
+    /// Use this to refresh views depending on the selection only when needed.
+    #[must_use]
+    pub fn selection_changed(&self) -> Option<&SelectionChange> {
+        self.events.iter().find_map(|event| match event {
+            EditorEvent::SelectionChanged(change) => Some(change),
+            _ => None,
+        })
+    }
+
     /// Checks if a specific hoverable UI element (node, pin, or link) is currently hovered by the mouse.
     #[doc(
         alias = "IsPinHovered",
@@ -110,46 +160,22 @@ impl OuterScope {
     ///
     /// Returns `Some(Link)` containing details about the new link if one was created,
     /// otherwise `None`.
+    ///
+    /// The link is normalized so that `start_pin` is always the output pin, even if the user
+    /// started dragging from the input pin. Links between two pins of the same kind are not
+    /// reported here, see [`OuterScope::try_links_created`].
     #[doc(alias = "IsLinkCreated", alias = "IsLinkCreated_IntPtr")]
     #[must_use]
     pub fn links_created(&self) -> Option<Link> {
-        let mut start_node_id: i32 = -1;
-        let mut start_pin_id: i32 = -1;
-        let mut end_node_id: i32 = -1;
-        let mut end_pin_id: i32 = -1;
-        // Note: The bool pointer is const in the C++ definition, but the C binding expects *mut.
-        // We initialize it and assume imnodes won't write through this specific pointer.
-        let mut created_from_snap: bool = false;
-
-        // Safety: C API call. All pointers are valid mutable references or a bool pointer.
-        let is_created = unsafe {
-            sys::imnodes_IsLinkCreated_IntPtr(
-                core::ptr::from_mut(&mut start_node_id),
-                core::ptr::from_mut(&mut start_pin_id),
-                core::ptr::from_mut(&mut end_node_id),
-                core::ptr::from_mut(&mut end_pin_id),
-                // Casting *mut bool is necessary due to bindgen's C interpretation.
-                core::ptr::from_mut::<bool>(&mut created_from_snap).cast(),
-            )
-        };
+        self.created_link.clone().and_then(Result::ok)
+    }
 
-        if is_created
-            && start_pin_id >= 0
-            && end_pin_id >= 0
-            && start_node_id >= 0
-            && end_node_id >= 0
-        {
-            Some(Link {
-                start_node: NodeId { id: start_node_id },
-                end_node: NodeId { id: end_node_id },
-                // We assume the start is always an output and the end is always an input.
-                start_pin: OutputPinId { id: start_pin_id },
-                end_pin: InputPinId { id: end_pin_id },
-                created_from_snap,
-            })
-        } else {
-            None
-        }
+    /// Like [`OuterScope::links_created`], but also reports links the user tried to create
+    /// between two pins that cannot be connected.
+    #[doc(alias = "IsLinkCreated", alias = "IsLinkCreated_IntPtr")]
+    #[must_use]
+    pub fn try_links_created(&self) -> Option<Result<Link, LinkError>> {
+        self.created_link.clone()
     }
 
     /// Checks if an existing link was detached (destroyed) by the user in this frame.
@@ -243,6 +269,119 @@ impl OuterScope {
     }
 }
 
+/// What was submitted to an editor in the current frame.
+#[derive(Debug, Default)]
+pub(crate) struct FrameState {
+    pub(crate) nodes: Vec<NodeId>,
+    pub(crate) pins: HashMap<PinId, SubmittedPin>,
+}
+
+/// A pin added through a [`NodeScope`] in the current frame.
+#[derive(Debug, Clone)]
+pub(crate) struct SubmittedPin {
+    /// The node the pin belongs to.
+    pub(crate) node: NodeId,
+    pub(crate) kind: PinKind,
+    pub(crate) pin_type: Option<Rc<dyn ErasedPinType>>,
+}
+
+/// Queries the link created in the frame that just ended.
+///
+/// imnodes reports the pin where the drag started first, which is the input pin if the user
+/// dragged from an input. The kinds of the pins submitted in this frame are used to swap the
+/// ends back into output to input order, and their [`PinType`]s to reject incompatible links.
+fn query_created_link(pins: &HashMap<PinId, SubmittedPin>) -> Option<Result<Link, LinkError>> {
+    let mut start_node_id: i32 = -1;
+    let mut start_pin_id: i32 = -1;
+    let mut end_node_id: i32 = -1;
+    let mut end_pin_id: i32 = -1;
+    // Note: The bool pointer is const in the C++ definition, but the C binding expects *mut.
+    // We initialize it and assume imnodes won't write through this specific pointer.
+    let mut created_from_snap: bool = false;
+
+    // Safety: C API call. All pointers are valid mutable references or a bool pointer.
+    let is_created = unsafe {
+        sys::imnodes_IsLinkCreated_IntPtr(
+            core::ptr::from_mut(&mut start_node_id),
+            core::ptr::from_mut(&mut start_pin_id),
+            core::ptr::from_mut(&mut end_node_id),
+            core::ptr::from_mut(&mut end_pin_id),
+            // Casting *mut bool is necessary due to bindgen's C interpretation.
+            core::ptr::from_mut::<bool>(&mut created_from_snap).cast(),
+        )
+    };
+
+    if !is_created || start_pin_id < 0 || end_pin_id < 0 || start_node_id < 0 || end_node_id < 0 {
+        return None;
+    }
+    Some(resolve_created_link(
+        pins,
+        (start_node_id, start_pin_id),
+        (end_node_id, end_pin_id),
+        created_from_snap,
+    ))
+}
+
+/// Turns the `(node, pin)` ends of a link reported by imnodes into a [`Link`], see
+/// [`query_created_link`].
+fn resolve_created_link(
+    pins: &HashMap<PinId, SubmittedPin>,
+    (start_node_id, start_pin_id): (i32, i32),
+    (end_node_id, end_pin_id): (i32, i32),
+    created_from_snap: bool,
+) -> Result<Link, LinkError> {
+    let start_pin = PinId { id: start_pin_id };
+    let end_pin = PinId { id: end_pin_id };
+    // Pins which were not submitted through a `NodeScope` are assumed to be in the usual
+    // output to input order.
+    let start_kind = pins.get(&start_pin).map(|pin| pin.kind).unwrap_or_else(|| {
+        pins.get(&end_pin)
+            .map_or(PinKind::Output, |pin| pin.kind.opposite())
+    });
+    let end_kind = pins
+        .get(&end_pin)
+        .map(|pin| pin.kind)
+        .unwrap_or_else(|| start_kind.opposite());
+
+    let link = match (start_kind, end_kind) {
+        (PinKind::Output, PinKind::Input) => Link {
+            start_node: NodeId { id: start_node_id },
+            end_node: NodeId { id: end_node_id },
+            start_pin: OutputPinId { id: start_pin_id },
+            end_pin: InputPinId { id: end_pin_id },
+            created_from_snap,
+        },
+        (PinKind::Input, PinKind::Output) => Link {
+            start_node: NodeId { id: end_node_id },
+            end_node: NodeId { id: start_node_id },
+            start_pin: OutputPinId { id: end_pin_id },
+            end_pin: InputPinId { id: start_pin_id },
+            created_from_snap,
+        },
+        (kind, _) => {
+            return Err(LinkError::SamePinKind {
+                start_pin,
+                end_pin,
+                kind,
+            });
+        }
+    };
+
+    // Links to pins without a type are always accepted.
+    let pin_type = |pin: PinId| pins.get(&pin).and_then(|pin| pin.pin_type.as_deref());
+    if let (Some(output), Some(input)) = (
+        pin_type(link.start_pin.into()),
+        pin_type(link.end_pin.into()),
+    ) && !output.can_link_to(input)
+    {
+        return Err(LinkError::IncompatiblePinTypes {
+            start_pin: link.start_pin,
+            end_pin: link.end_pin,
+        });
+    }
+    Ok(link)
+}
+
 /// Begins the node editor UI definition.
 ///
 /// Call methods on the provided [`EditorScope`] to add nodes and links.
@@ -250,45 +389,172 @@ impl OuterScope {
 /// to query events like link creation or destruction.
 ///
 /// Requires the [`EditorContext`] to be set via [`EditorContext::set_as_current_editor`] beforehand.
+///
+/// The style and IO settings owned by the editor, see [`EditorContext::set_own_style`] and
+/// [`EditorContext::own_io`], replace the shared ones until the editor has ended.
 #[doc(alias = "BeginNodeEditor", alias = "EndNodeEditor")]
-pub fn editor<F: FnOnce(EditorScope)>(context: &mut EditorContext, f: F) -> OuterScope {
+pub fn editor<'cb, F: FnOnce(EditorScope<'_, 'cb>)>(
+    context: &mut EditorContext,
+    f: F,
+) -> OuterScope {
     // Ensure the context is set (though the user should ideally do this explicitly)
     let _ = context.set_as_current_editor();
 
+    // Nodes and pins are submitted again every frame.
+    context.frame.nodes.clear();
+    context.frame.pins.clear();
+
+    // Restored after the editor has ended, so the settings only apply to this editor.
+    let shared_settings = context.apply_own_settings();
+
     // Safety: Begins the editor scope. Must be paired with EndNodeEditor.
     unsafe { sys::imnodes_BeginNodeEditor() };
-    f(EditorScope {});
-    // Safety: Ends the editor scope.
+    context.set_drawn(true);
+    // imnodes places the canvas origin at the cursor position of its freshly opened child window.
+    let mut origin = imgui::sys::ImVec2 { x: 0.0, y: 0.0 };
+    // Safety: C API call. `origin` is written to by the function.
+    unsafe { imgui::sys::igGetCursorScreenPos(core::ptr::from_mut(&mut origin)) };
+    context.canvas_origin = [origin.x, origin.y];
+    let mut mini_map_hovering = None;
+    f(EditorScope {
+        context: &mut *context,
+        mini_map_hovering: &mut mini_map_hovering,
+    });
+    // Safety: Ends the editor scope. Calls the minimap callback, which is still alive.
     unsafe { sys::imnodes_EndNodeEditor() };
-    OuterScope {}
+    context.set_drawn(false);
+    drop(mini_map_hovering);
+    context.restore_settings(shared_settings);
+
+    let mut outer_scope = OuterScope {
+        events: Vec::new(),
+        created_link: query_created_link(&context.frame.pins),
+    };
+    context.hovered = outer_scope
+        .get_hovered_pin()
+        .map(ContextMenuTarget::Pin)
+        .or_else(|| outer_scope.get_hovered_link().map(ContextMenuTarget::Link))
+        .or_else(|| get_hovered_node().map(ContextMenuTarget::Node));
+
+    // Safety: Reads the mouse state from the IO of the current imgui context.
+    let (mouse_pos, mouse_down) = unsafe {
+        let io = imgui::sys::igGetIO();
+        ((*io).MousePos, (*io).MouseDown[0])
+    };
+    // Links are dragged with the left mouse button.
+    if let Some(from) = outer_scope.from_where_link_started() {
+        context.link_drag = context.frame.pins.get(&from).map(|pin| (from, pin.clone()));
+    } else if !mouse_down {
+        context.link_drag = None;
+    }
+    let mouse_pos = [mouse_pos.x, mouse_pos.y];
+    let mouse_grid_pos = context.screen_to_grid_space(mouse_pos);
+    outer_scope.events =
+        context
+            .events
+            .capture(&outer_scope, &context.frame, mouse_pos, mouse_grid_pos);
+    outer_scope
 }
 
 /// Represents the scope within the main node editor block (`imnodes::editor`).
 /// Use methods on this struct to add nodes, links, and the minimap.
+///
+/// `'cb` is the lifetime of the data the minimap callback may borrow, see
+/// [`EditorScope::add_mini_map_with_node_hovering`].
 #[derive(Debug)]
-pub struct EditorScope {}
-impl EditorScope {
+pub struct EditorScope<'a, 'cb> {
+    pub(crate) context: &'a mut EditorContext,
+    // Owned by `editor`, since imnodes calls it in `EndNodeEditor`.
+    mini_map_hovering: &'a mut Option<MiniMapCallback<'cb>>,
+}
+
+/// The closure passed to [`EditorScope::add_mini_map_with_node_hovering`].
+struct MiniMapCallback<'cb>(Box<dyn FnMut(NodeId) + 'cb>);
+
+impl std::fmt::Debug for MiniMapCallback<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("MiniMapCallback")
+    }
+}
+
+/// Forwards the minimap node hovering callback of imnodes to the [`MiniMapCallback`] in `user_data`.
+unsafe extern "C" fn mini_map_node_hovering(node_id: i32, user_data: *mut core::ffi::c_void) {
+    // Safety: `user_data` points to the callback stored by `add_mini_map_with_node_hovering`,
+    // which `editor` keeps alive until `EndNodeEditor` has returned.
+    let callback = unsafe { &mut *user_data.cast::<MiniMapCallback<'_>>() };
+    (callback.0)(NodeId { id: node_id });
+}
+
+impl<'cb> EditorScope<'_, 'cb> {
+    /// Returns a handle to operate on the node with the given ID in this editor.
+    ///
+    /// Equivalent to [`EditorContext::node`], which is not reachable while the editor is borrowed.
+    #[must_use]
+    pub fn node(&self, id: NodeId) -> NodeHandle<'_> {
+        self.context.node(id)
+    }
+
+    /// Returns a handle to operate on the link with the given ID in this editor.
+    ///
+    /// Equivalent to [`EditorContext::link`], which is not reachable while the editor is borrowed.
+    #[must_use]
+    pub fn link(&self, id: LinkId) -> LinkHandle<'_> {
+        self.context.link(id)
+    }
+
     /// Adds an interactive minimap overlay to the editor canvas.
     ///
     /// Must be called just before the end of the [`editor`] closure.
+    /// See [`EditorScope::add_mini_map_with_node_hovering`] to react to hovered nodes.
     ///
     /// # Arguments
     /// * `size_fraction`: The size of the minimap relative to the editor canvas (e.g., 0.2 for 20%).
     /// * `location`: The corner where the minimap should be placed.
     #[doc(alias = "MiniMap")]
     pub fn add_mini_map(&mut self, size_fraction: f32, location: MiniMapLocation) {
-        // The C API allows a callback, but wrapping it safely with Rust closures
-        // and void pointers is complex. We omit it for now.
-        let node_hovering_callback = None;
-        let node_hovering_callback_data = core::ptr::null_mut::<core::ffi::c_void>();
-
         // Safety: C API call within the editor scope.
+        unsafe {
+            sys::imnodes_MiniMap(size_fraction, location as i32, None, core::ptr::null_mut());
+        }
+    }
+
+    /// Adds an interactive minimap overlay which calls `on_node_hovered` with the node
+    /// the user hovers in the minimap, e.g. to show a tooltip.
+    ///
+    /// The callback runs when the editor ends, after the [`editor`] closure has returned, so it
+    /// can borrow anything that outlives the call to [`editor`]. A panic in the callback aborts
+    /// the process, as it is called from C++.
+    ///
+    /// ```no_run
+    /// # let ui: &imgui::Ui = unimplemented!();
+    /// # let mut editor_context: imnodes::EditorContext = unimplemented!();
+    /// imnodes::editor(&mut editor_context, |mut editor_scope| {
+    ///     let location = imnodes::MiniMapLocation::BottomRight;
+    ///     editor_scope.add_mini_map_with_node_hovering(0.2, location, |node| {
+    ///         ui.tooltip_text(format!("{node:?}"));
+    ///     });
+    /// });
+    /// ```
+    #[doc(alias = "MiniMap")]
+    pub fn add_mini_map_with_node_hovering<H>(
+        &mut self,
+        size_fraction: f32,
+        location: MiniMapLocation,
+        on_node_hovered: H,
+    ) where
+        H: FnMut(NodeId) + 'cb,
+    {
+        let callback = self
+            .mini_map_hovering
+            .insert(MiniMapCallback(Box::new(on_node_hovered)));
+        // Safety: C API call within the editor scope. The callback stays alive until the
+        // editor has ended, the only time imnodes calls it.
         unsafe {
             sys::imnodes_MiniMap(
                 size_fraction,
                 location as i32,
-                node_hovering_callback,
-                node_hovering_callback_data,
+                Some(mini_map_node_hovering),
+                core::ptr::from_mut(callback).cast(),
             );
         }
     }
@@ -305,7 +571,12 @@ impl EditorScope {
     pub fn add_node<F: FnOnce(NodeScope)>(&mut self, id: NodeId, f: F) {
         // Safety: Begins a node scope. Must be paired with EndNode.
         unsafe { sys::imnodes_BeginNode(id.into()) }
-        f(NodeScope {});
+        self.context.frame.nodes.push(id);
+        f(NodeScope {
+            id,
+            pins: &mut self.context.frame.pins,
+            link_drag: self.context.link_drag.as_ref(),
+        });
         // Safety: Ends the node scope.
         unsafe {
             sys::imnodes_EndNode();
@@ -358,8 +629,77 @@ impl EditorScope {
 /// Represents the scope within a node definition block (`add_node`).
 /// Use methods on this struct to add title bars, input/output pins, and static attributes.
 #[derive(Debug)]
-pub struct NodeScope {}
-impl NodeScope {
+pub struct NodeScope<'a> {
+    id: NodeId,
+    pins: &'a mut HashMap<PinId, SubmittedPin>,
+    link_drag: Option<&'a (PinId, SubmittedPin)>,
+}
+impl NodeScope<'_> {
+    /// Adds an input or output pin and the attribute UI built by `f`.
+    fn add_pin<F: FnOnce()>(
+        &mut self,
+        id: PinId,
+        kind: PinKind,
+        shape: PinShape,
+        pin_type: Option<Rc<dyn ErasedPinType>>,
+        f: F,
+    ) {
+        let greyed_out = pin_type
+            .as_deref()
+            .is_some_and(|pin_type| !self.accepts_dragged_link(id, kind, pin_type));
+        self.pins.insert(
+            id,
+            SubmittedPin {
+                node: self.id,
+                kind,
+                pin_type,
+            },
+        );
+
+        if greyed_out {
+            push_greyed_out_pin_colors();
+        }
+        match kind {
+            // Safety: Begins an input attribute scope. Must be paired with EndInputAttribute.
+            PinKind::Input => unsafe { sys::imnodes_BeginInputAttribute(id.id, shape as i32) },
+            // Safety: Begins an output attribute scope. Must be paired with EndOutputAttribute.
+            PinKind::Output => unsafe { sys::imnodes_BeginOutputAttribute(id.id, shape as i32) },
+        }
+        // imnodes copies the pin colors when the attribute begins.
+        if greyed_out {
+            // Safety: Pops the colors pushed above.
+            unsafe {
+                sys::imnodes_PopColorStyle();
+                sys::imnodes_PopColorStyle();
+            }
+        }
+        f();
+        match kind {
+            // Safety: Ends the input attribute scope.
+            PinKind::Input => unsafe { sys::imnodes_EndInputAttribute() },
+            // Safety: Ends the output attribute scope.
+            PinKind::Output => unsafe { sys::imnodes_EndOutputAttribute() },
+        }
+    }
+
+    /// Returns whether the link the user is dragging, if any, can be connected to this pin.
+    fn accepts_dragged_link(&self, id: PinId, kind: PinKind, pin_type: &dyn ErasedPinType) -> bool {
+        let Some((from, from_pin)) = self.link_drag else {
+            return true;
+        };
+        if *from == id {
+            return true;
+        }
+        if from_pin.kind == kind {
+            return false;
+        }
+        match (from_pin.pin_type.as_deref(), kind) {
+            (None, _) => true,
+            (Some(from_type), PinKind::Input) => from_type.can_link_to(pin_type),
+            (Some(from_type), PinKind::Output) => pin_type.can_link_to(from_type),
+        }
+    }
+
     /// Adds a title bar to the node.
     ///
     /// Place ImGui UI elements for the title bar (e.g., `ui.text("Title")`) within the closure `f`.
@@ -384,11 +724,24 @@ impl NodeScope {
     /// * `f`: A closure that defines the UI content associated with this pin.
     #[doc(alias = "BeginInputAttribute", alias = "EndInputAttribute")]
     pub fn add_input<F: FnOnce()>(&mut self, id: InputPinId, shape: PinShape, f: F) {
-        // Safety: Begins an input attribute scope. Must be paired with EndInputAttribute.
-        unsafe { sys::imnodes_BeginInputAttribute(id.into(), shape as i32) };
-        f();
-        // Safety: Ends the input attribute scope.
-        unsafe { sys::imnodes_EndInputAttribute() };
+        self.add_pin(id.into(), PinKind::Input, shape, None, f);
+    }
+
+    /// Like [`NodeScope::add_input`], but only accepts links from outputs whose [`PinType`]
+    /// can be linked to `pin_type`.
+    ///
+    /// Links from incompatible outputs are reported as [`LinkError::IncompatiblePinTypes`] by
+    /// [`OuterScope::try_links_created`], and the pin is greyed out while the user drags such a
+    /// link. Links from untyped outputs are accepted.
+    #[doc(alias = "BeginInputAttribute", alias = "EndInputAttribute")]
+    pub fn add_typed_input<T: PinType, F: FnOnce()>(
+        &mut self,
+        id: InputPinId,
+        shape: PinShape,
+        pin_type: T,
+        f: F,
+    ) {
+        self.add_pin(id.into(), PinKind::Input, shape, Some(Rc::new(pin_type)), f);
     }
 
     /// Adds an output pin (rendered on the right side) and its associated attribute UI to the node.
@@ -401,11 +754,26 @@ impl NodeScope {
     /// * `f`: A closure that defines the UI content associated with this pin.
     #[doc(alias = "BeginOutputAttribute", alias = "EndOutputAttribute")]
     pub fn add_output<F: FnOnce()>(&mut self, id: OutputPinId, shape: PinShape, f: F) {
-        // Safety: Begins an output attribute scope. Must be paired with EndOutputAttribute.
-        unsafe { sys::imnodes_BeginOutputAttribute(id.into(), shape as i32) };
-        f();
-        // Safety: Ends the output attribute scope.
-        unsafe { sys::imnodes_EndOutputAttribute() };
+        self.add_pin(id.into(), PinKind::Output, shape, None, f);
+    }
+
+    /// Like [`NodeScope::add_output`], but only links to inputs whose [`PinType`] `pin_type`
+    /// can be linked to, see [`NodeScope::add_typed_input`].
+    #[doc(alias = "BeginOutputAttribute", alias = "EndOutputAttribute")]
+    pub fn add_typed_output<T: PinType, F: FnOnce()>(
+        &mut self,
+        id: OutputPinId,
+        shape: PinShape,
+        pin_type: T,
+        f: F,
+    ) {
+        self.add_pin(
+            id.into(),
+            PinKind::Output,
+            shape,
+            Some(Rc::new(pin_type)),
+            f,
+        );
     }
 
     /// Adds a static attribute (UI element without a pin) to the node.
@@ -426,3 +794,173 @@ impl NodeScope {
         unsafe { sys::imnodes_EndStaticAttribute() };
     }
 }
+
+/// Pushes grey versions of the current pin colors, for pins the dragged link cannot be linked to.
+fn push_greyed_out_pin_colors() {
+    for item in [ColorStyle::Pin, ColorStyle::PinHovered] {
+        // Safety: C API calls within the editor scope. The style of the current context is alive.
+        unsafe {
+            let [r, g, b, a] =
+                ImColor32::from_bits((*sys::imnodes_GetStyle()).Colors[item as usize]).to_rgba();
+            let grey = ((u16::from(r) + u16::from(g) + u16::from(b)) / 3) as u8;
+            let color = ImColor32::from_rgba(grey, grey, grey, a / 2);
+            sys::imnodes_PushColorStyle(item as i32, color.into());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    enum Value {
+        Float,
+        Vector,
+    }
+
+    impl PinType for Value {
+        fn can_link_to(&self, input: &Self) -> bool {
+            self == input || (*self == Self::Float && *input == Self::Vector)
+        }
+    }
+
+    #[derive(Debug)]
+    struct Other;
+
+    impl PinType for Other {
+        fn can_link_to(&self, _: &Self) -> bool {
+            true
+        }
+    }
+
+    /// Pins 1 and 2 are an output and an input of node 10, pin 3 is an input of node 20.
+    fn submitted_pins(types: [Option<Rc<dyn ErasedPinType>>; 3]) -> HashMap<PinId, SubmittedPin> {
+        let [first, second, third] = types;
+        [
+            (1, 10, PinKind::Output, first),
+            (2, 10, PinKind::Input, second),
+            (3, 20, PinKind::Input, third),
+        ]
+        .into_iter()
+        .map(|(pin, node, kind, pin_type)| {
+            let pin = PinId { id: pin };
+            let node = NodeId { id: node };
+            (
+                pin,
+                SubmittedPin {
+                    node,
+                    kind,
+                    pin_type,
+                },
+            )
+        })
+        .collect()
+    }
+
+    fn link(start_node: i32, start_pin: i32, end_node: i32, end_pin: i32) -> Link {
+        Link {
+            start_node: NodeId { id: start_node },
+            end_node: NodeId { id: end_node },
+            start_pin: OutputPinId { id: start_pin },
+            end_pin: InputPinId { id: end_pin },
+            created_from_snap: false,
+        }
+    }
+
+    #[test]
+    fn keeps_links_dragged_from_an_output() {
+        let pins = submitted_pins([None, None, None]);
+        assert_eq!(
+            resolve_created_link(&pins, (10, 1), (20, 3), false),
+            Ok(link(10, 1, 20, 3))
+        );
+    }
+
+    #[test]
+    fn swaps_links_dragged_from_an_input() {
+        let pins = submitted_pins([None, None, None]);
+        assert_eq!(
+            resolve_created_link(&pins, (20, 3), (10, 1), true),
+            Ok(Link {
+                created_from_snap: true,
+                ..link(10, 1, 20, 3)
+            })
+        );
+    }
+
+    #[test]
+    fn assumes_output_to_input_order_for_unknown_pins() {
+        let pins = submitted_pins([None, None, None]);
+        assert_eq!(
+            resolve_created_link(&pins, (30, 7), (40, 8), false),
+            Ok(link(30, 7, 40, 8))
+        );
+        // The kind of the known end decides.
+        assert_eq!(
+            resolve_created_link(&pins, (30, 7), (10, 1), false),
+            Ok(link(10, 1, 30, 7))
+        );
+    }
+
+    #[test]
+    fn rejects_pins_of_the_same_kind() {
+        let pins = submitted_pins([None, None, None]);
+        assert_eq!(
+            resolve_created_link(&pins, (10, 2), (20, 3), false),
+            Err(LinkError::SamePinKind {
+                start_pin: PinId { id: 2 },
+                end_pin: PinId { id: 3 },
+                kind: PinKind::Input,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_incompatible_pin_types() {
+        let incompatible = Err(LinkError::IncompatiblePinTypes {
+            start_pin: OutputPinId { id: 1 },
+            end_pin: InputPinId { id: 3 },
+        });
+
+        let pins = submitted_pins([
+            Some(Rc::new(Value::Vector)),
+            None,
+            Some(Rc::new(Value::Float)),
+        ]);
+        assert_eq!(
+            resolve_created_link(&pins, (10, 1), (20, 3), false),
+            incompatible
+        );
+        // The types are checked after swapping the ends.
+        assert_eq!(
+            resolve_created_link(&pins, (20, 3), (10, 1), false),
+            incompatible
+        );
+
+        let pins = submitted_pins([Some(Rc::new(Other)), None, Some(Rc::new(Value::Float))]);
+        assert_eq!(
+            resolve_created_link(&pins, (10, 1), (20, 3), false),
+            incompatible
+        );
+    }
+
+    #[test]
+    fn accepts_compatible_and_untyped_pins() {
+        let pins = submitted_pins([
+            Some(Rc::new(Value::Float)),
+            None,
+            Some(Rc::new(Value::Vector)),
+        ]);
+        assert_eq!(
+            resolve_created_link(&pins, (20, 3), (10, 1), false),
+            Ok(link(10, 1, 20, 3))
+        );
+
+        let pins = submitted_pins([Some(Rc::new(Value::Vector)), None, None]);
+        assert_eq!(
+            resolve_created_link(&pins, (10, 1), (20, 3), false),
+            Ok(link(10, 1, 20, 3))
+        );
+    }
+}
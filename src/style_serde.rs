@@ -0,0 +1,294 @@
+/*!
+`serde` support for [`Style`], enabled with the `serde` feature.
+
+Every style variable is stored under its snake case name, the flags as a `|` separated list
+and the colors as a map from [`ColorStyle`] names to either `"#rrggbbaa"` hex strings
+(`"#rrggbb"` for opaque colors) or `[r, g, b, a]` arrays. A theme file looks like:
+
+```json
+{
+    "grid_spacing": 32.0,
+    "node_padding": [8.0, 4.0],
+    "flags": "NODE_OUTLINE | GRID_LINES | GRID_SNAPPING",
+    "colors": {
+        "TitleBar": "#29628a",
+        "GridBackground": [40, 40, 50, 255]
+    }
+}
+```
+
+Missing entries keep the value of [`Style::default`], so themes only need to list what they change.
+*/
+
+use crate::{ColorStyle, ImVec2, Style, StyleFlags};
+use imgui::ImColor32;
+use serde::de::Error as _;
+use serde::ser::SerializeMap as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
+
+impl Serialize for Style {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        StyleData::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Style {
+    /// Deserializes a possibly partial style on top of [`Style::default`].
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        StyleData::deserialize(deserializer).map(Self::from)
+    }
+}
+
+/// The serialized layout of a [`Style`].
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct StyleData {
+    grid_spacing: f32,
+    node_corner_rounding: f32,
+    node_padding: [f32; 2],
+    node_border_thickness: f32,
+    link_thickness: f32,
+    link_line_segments_per_length: f32,
+    link_hover_distance: f32,
+    pin_circle_radius: f32,
+    pin_quad_side_length: f32,
+    pin_triangle_side_length: f32,
+    pin_line_thickness: f32,
+    pin_hover_radius: f32,
+    pin_offset: f32,
+    mini_map_padding: [f32; 2],
+    mini_map_offset: [f32; 2],
+    flags: StyleFlags,
+    colors: Colors,
+}
+
+impl Default for StyleData {
+    fn default() -> Self {
+        Self::from(&Style::default())
+    }
+}
+
+impl From<&Style> for StyleData {
+    fn from(style: &Style) -> Self {
+        let vec2 = |value: ImVec2| [value.x, value.y];
+        Self {
+            grid_spacing: style.grid_spacing(),
+            node_corner_rounding: style.node_corner_rounding(),
+            node_padding: vec2(style.node_padding()),
+            node_border_thickness: style.node_border_thickness(),
+            link_thickness: style.link_thickness(),
+            link_line_segments_per_length: style.link_line_segments_per_length(),
+            link_hover_distance: style.link_hover_distance(),
+            pin_circle_radius: style.pin_circle_radius(),
+            pin_quad_side_length: style.pin_quad_side_length(),
+            pin_triangle_side_length: style.pin_triangle_side_length(),
+            pin_line_thickness: style.pin_line_thickness(),
+            pin_hover_radius: style.pin_hover_radius(),
+            pin_offset: style.pin_offset(),
+            mini_map_padding: vec2(style.mini_map_padding()),
+            mini_map_offset: vec2(style.mini_map_offset()),
+            flags: style.flags(),
+            colors: Colors(
+                ColorStyle::ALL
+                    .iter()
+                    .map(|&item| (item, style.color(item)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl From<StyleData> for Style {
+    fn from(data: StyleData) -> Self {
+        let vec2 = |[x, y]: [f32; 2]| ImVec2 { x, y };
+        let mut style = Style::default();
+        let _ = style
+            .set_grid_spacing(data.grid_spacing)
+            .set_node_corner_rounding(data.node_corner_rounding)
+            .set_node_padding(vec2(data.node_padding))
+            .set_node_border_thickness(data.node_border_thickness)
+            .set_link_thickness(data.link_thickness)
+            .set_link_line_segments_per_length(data.link_line_segments_per_length)
+            .set_link_hover_distance(data.link_hover_distance)
+            .set_pin_circle_radius(data.pin_circle_radius)
+            .set_pin_quad_side_length(data.pin_quad_side_length)
+            .set_pin_triangle_side_length(data.pin_triangle_side_length)
+            .set_pin_line_thickness(data.pin_line_thickness)
+            .set_pin_hover_radius(data.pin_hover_radius)
+            .set_pin_offset(data.pin_offset)
+            .set_mini_map_padding(vec2(data.mini_map_padding))
+            .set_mini_map_offset(vec2(data.mini_map_offset))
+            .set_flags(data.flags);
+        // Colors missing from the data keep their default.
+        for (item, color) in data.colors.0 {
+            let _ = style.set_color(item, color);
+        }
+        style
+    }
+}
+
+/// Colors by [`ColorStyle`], serialized as a map in the order of [`ColorStyle::ALL`].
+#[derive(Default)]
+struct Colors(Vec<(ColorStyle, ImColor32)>);
+
+impl Serialize for Colors {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (item, color) in &self.0 {
+            map.serialize_entry(item, &Color(*color))?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Colors {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let colors = HashMap::<ColorStyle, Color>::deserialize(deserializer)?;
+        Ok(Self(
+            colors
+                .into_iter()
+                .map(|(item, Color(color))| (item, color))
+                .collect(),
+        ))
+    }
+}
+
+/// A color serialized as `"#rrggbb"` if opaque and as `"#rrggbbaa"` otherwise, deserialized from
+/// a hex string or an `[r, g, b, a]` array.
+struct Color(ImColor32);
+
+impl Serialize for Color {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let [r, g, b, a] = self.0.to_rgba();
+        let hex = if a == u8::MAX {
+            format!("#{r:02x}{g:02x}{b:02x}")
+        } else {
+            format!("#{r:02x}{g:02x}{b:02x}{a:02x}")
+        };
+        serializer.serialize_str(&hex)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ColorRepr {
+    Hex(String),
+    Rgba([u8; 4]),
+}
+
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let [r, g, b, a] = match ColorRepr::deserialize(deserializer)? {
+            ColorRepr::Hex(hex) => parse_hex(&hex).ok_or_else(|| {
+                D::Error::custom(format!(
+                    "invalid color `{hex}`, expected `#rrggbb` or `#rrggbbaa`"
+                ))
+            })?,
+            ColorRepr::Rgba(rgba) => rgba,
+        };
+        Ok(Self(ImColor32::from_rgba(r, g, b, a)))
+    }
+}
+
+/// Parses `#rrggbb` or `#rrggbbaa`.
+fn parse_hex(hex: &str) -> Option<[u8; 4]> {
+    let digits = hex.strip_prefix('#')?;
+    if !digits.is_ascii() || !matches!(digits.len(), 6 | 8) {
+        return None;
+    }
+    let mut rgba = [u8::MAX; 4];
+    for (channel, chunk) in rgba.iter_mut().zip(digits.as_bytes().chunks(2)) {
+        *channel = u8::from_str_radix(std::str::from_utf8(chunk).ok()?, 16).ok()?;
+    }
+    Some(rgba)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_exactly() {
+        let mut style = Style::default();
+        let _ = style
+            .set_grid_spacing(0.1 + 0.2)
+            .set_node_padding(ImVec2 { x: 1.5, y: -2.0 })
+            .set_pin_offset(f32::MIN_POSITIVE)
+            .set_flags(StyleFlags::GRID_LINES | StyleFlags::GRID_SNAPPING)
+            .set_color(ColorStyle::TitleBar, ImColor32::from_rgba(1, 2, 3, 4))
+            .set_color(ColorStyle::Link, ImColor32::from_rgba(250, 128, 0, 255));
+
+        let json = serde_json::to_string(&style).unwrap();
+        let loaded: Style = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&loaded).unwrap(), json);
+        assert_eq!(loaded.grid_spacing(), 0.1 + 0.2);
+        assert_eq!(loaded.pin_offset(), f32::MIN_POSITIVE);
+        assert_eq!(loaded.flags(), style.flags());
+        for item in ColorStyle::ALL {
+            assert_eq!(loaded.color(item), style.color(item));
+        }
+    }
+
+    #[test]
+    fn writes_opaque_colors_without_alpha() {
+        let mut style = Style::default();
+        let _ = style
+            .set_color(
+                ColorStyle::TitleBar,
+                ImColor32::from_rgba(0x29, 0x62, 0x8a, 0xff),
+            )
+            .set_color(
+                ColorStyle::Link,
+                ImColor32::from_rgba(0x29, 0x62, 0x8a, 0x80),
+            );
+        let json = serde_json::to_value(&style).unwrap();
+        assert_eq!(json["colors"]["TitleBar"], "#29628a");
+        assert_eq!(json["colors"]["Link"], "#29628a80");
+    }
+
+    #[test]
+    fn loads_partial_styles_on_top_of_the_default() {
+        let style: Style = serde_json::from_str(
+            r##"{
+                "grid_spacing": 32.0,
+                "flags": "GRID_LINES",
+                "colors": {
+                    "Link": "#ff000080",
+                    "Pin": [1, 2, 3, 4],
+                    "PinHovered": "#010203"
+                }
+            }"##,
+        )
+        .unwrap();
+        let default = Style::default();
+
+        assert_eq!(style.grid_spacing(), 32.0);
+        assert_eq!(style.flags(), StyleFlags::GRID_LINES);
+        assert_eq!(style.color(ColorStyle::Link).to_rgba(), [255, 0, 0, 128]);
+        assert_eq!(style.color(ColorStyle::Pin).to_rgba(), [1, 2, 3, 4]);
+        assert_eq!(
+            style.color(ColorStyle::PinHovered).to_rgba(),
+            [1, 2, 3, 255]
+        );
+        assert_eq!(style.pin_offset(), default.pin_offset());
+        assert_eq!(style.node_padding().x, default.node_padding().x);
+        assert_eq!(
+            style.color(ColorStyle::TitleBar),
+            default.color(ColorStyle::TitleBar)
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_entries() {
+        for json in [
+            r##"{"colors": {"Unknown": "#000000"}}"##,
+            r##"{"colors": {"Link": "#00000"}}"##,
+            r##"{"colors": {"Link": "000000"}}"##,
+            r#"{"grid_spacin": 1.0}"#,
+            r#"{"flags": "NO_SUCH_FLAG"}"#,
+        ] {
+            assert!(serde_json::from_str::<Style>(json).is_err(), "{json}");
+        }
+    }
+}
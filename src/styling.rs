@@ -1,5 +1,34 @@
-use crate::{EditorContext, sys};
+use crate::{EditorContext, EditorScope, ImVec2, sys};
 use imgui::ImColor32;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static AUTO_POP_TOKENS: AtomicBool = AtomicBool::new(false);
+
+/// Makes [`ColorToken`], [`StyleVarToken`] and [`AttributeFlagToken`] pop themselves when
+/// dropped without calling `pop()`, instead of panicking.
+///
+/// Disabled by default, since an unpopped token is usually a bug. In debug builds, a warning
+/// is printed to stderr for every token popped automatically.
+///
+/// Tokens dropped while unwinding from a panic always pop themselves.
+pub fn set_auto_pop_tokens(enabled: bool) {
+    AUTO_POP_TOKENS.store(enabled, Ordering::Relaxed);
+}
+
+/// Called when `token` is dropped without being popped, before popping it automatically.
+///
+/// Panics with `message` unless auto popping is enabled or the thread is already panicking.
+fn unpopped_token(token: &str, message: &str) {
+    if std::thread::panicking() {
+        return;
+    }
+    assert!(
+        AUTO_POP_TOKENS.load(Ordering::Relaxed),
+        "`{token}` was dropped without calling `pop()`. {message}"
+    );
+    #[cfg(debug_assertions)]
+    eprintln!("imnodes: `{token}` was dropped without calling `pop()`, popping it automatically");
+}
 
 // Re-export the underlying sys type for IO
 
@@ -36,7 +65,7 @@ impl Default for Style {
             PinOffset: 0.0,
             MiniMapPadding: sys::ImVec2 { x: 8.0, y: 8.0 },
             MiniMapOffset: sys::ImVec2 { x: 4.0, y: 4.0 },
-            Flags: (StyleFlags::GridLines as i32) | (StyleFlags::NodeOutline as i32),
+            Flags: (StyleFlags::GRID_LINES | StyleFlags::NODE_OUTLINE).bits(),
             // Initialize colors array temporarily
             Colors: [0; sys::ImNodesCol__ImNodesCol_COUNT as usize],
         };
@@ -48,6 +77,368 @@ impl Default for Style {
     }
 }
 
+/// Typed access to the style variables, colors and flags.
+impl Style {
+    /// Spacing between grid lines.
+    #[must_use]
+    pub fn grid_spacing(&self) -> f32 {
+        self.0.GridSpacing
+    }
+
+    /// Sets the spacing between grid lines.
+    pub fn set_grid_spacing(&mut self, value: f32) -> &mut Self {
+        self.0.GridSpacing = value;
+        self
+    }
+
+    /// Corner rounding radius for nodes.
+    #[must_use]
+    pub fn node_corner_rounding(&self) -> f32 {
+        self.0.NodeCornerRounding
+    }
+
+    /// Sets the corner rounding radius for nodes.
+    pub fn set_node_corner_rounding(&mut self, value: f32) -> &mut Self {
+        self.0.NodeCornerRounding = value;
+        self
+    }
+
+    /// Padding inside nodes.
+    #[must_use]
+    pub fn node_padding(&self) -> ImVec2 {
+        self.0.NodePadding
+    }
+
+    /// Sets the padding inside nodes.
+    pub fn set_node_padding(&mut self, value: ImVec2) -> &mut Self {
+        self.0.NodePadding = value;
+        self
+    }
+
+    /// Thickness of node borders.
+    #[must_use]
+    pub fn node_border_thickness(&self) -> f32 {
+        self.0.NodeBorderThickness
+    }
+
+    /// Sets the thickness of node borders.
+    pub fn set_node_border_thickness(&mut self, value: f32) -> &mut Self {
+        self.0.NodeBorderThickness = value;
+        self
+    }
+
+    /// Thickness of links between pins.
+    #[must_use]
+    pub fn link_thickness(&self) -> f32 {
+        self.0.LinkThickness
+    }
+
+    /// Sets the thickness of links between pins.
+    pub fn set_link_thickness(&mut self, value: f32) -> &mut Self {
+        self.0.LinkThickness = value;
+        self
+    }
+
+    /// Number of line segments used to render links per unit of length.
+    #[must_use]
+    pub fn link_line_segments_per_length(&self) -> f32 {
+        self.0.LinkLineSegmentsPerLength
+    }
+
+    /// Sets the number of line segments used to render links per unit of length.
+    pub fn set_link_line_segments_per_length(&mut self, value: f32) -> &mut Self {
+        self.0.LinkLineSegmentsPerLength = value;
+        self
+    }
+
+    /// Distance threshold for detecting link hovering.
+    #[must_use]
+    pub fn link_hover_distance(&self) -> f32 {
+        self.0.LinkHoverDistance
+    }
+
+    /// Sets the distance threshold for detecting link hovering.
+    pub fn set_link_hover_distance(&mut self, value: f32) -> &mut Self {
+        self.0.LinkHoverDistance = value;
+        self
+    }
+
+    /// The circle radius of circle pins.
+    #[must_use]
+    pub fn pin_circle_radius(&self) -> f32 {
+        self.0.PinCircleRadius
+    }
+
+    /// Sets the circle radius of circle pins.
+    pub fn set_pin_circle_radius(&mut self, value: f32) -> &mut Self {
+        self.0.PinCircleRadius = value;
+        self
+    }
+
+    /// The quad side length of quad pins.
+    #[must_use]
+    pub fn pin_quad_side_length(&self) -> f32 {
+        self.0.PinQuadSideLength
+    }
+
+    /// Sets the quad side length of quad pins.
+    pub fn set_pin_quad_side_length(&mut self, value: f32) -> &mut Self {
+        self.0.PinQuadSideLength = value;
+        self
+    }
+
+    /// The equilateral triangle side length of triangle pins.
+    #[must_use]
+    pub fn pin_triangle_side_length(&self) -> f32 {
+        self.0.PinTriangleSideLength
+    }
+
+    /// Sets the equilateral triangle side length of triangle pins.
+    pub fn set_pin_triangle_side_length(&mut self, value: f32) -> &mut Self {
+        self.0.PinTriangleSideLength = value;
+        self
+    }
+
+    /// The thickness of the line of pins which are not filled.
+    #[must_use]
+    pub fn pin_line_thickness(&self) -> f32 {
+        self.0.PinLineThickness
+    }
+
+    /// Sets the thickness of the line of pins which are not filled.
+    pub fn set_pin_line_thickness(&mut self, value: f32) -> &mut Self {
+        self.0.PinLineThickness = value;
+        self
+    }
+
+    /// The radius around the pin's center within which it is hovered.
+    #[must_use]
+    pub fn pin_hover_radius(&self) -> f32 {
+        self.0.PinHoverRadius
+    }
+
+    /// Sets the radius around the pin's center within which it is hovered.
+    pub fn set_pin_hover_radius(&mut self, value: f32) -> &mut Self {
+        self.0.PinHoverRadius = value;
+        self
+    }
+
+    /// Horizontal offset of the pins from the edge of the node.
+    #[must_use]
+    pub fn pin_offset(&self) -> f32 {
+        self.0.PinOffset
+    }
+
+    /// Sets the horizontal offset of the pins from the edge of the node.
+    pub fn set_pin_offset(&mut self, value: f32) -> &mut Self {
+        self.0.PinOffset = value;
+        self
+    }
+
+    /// Padding inside the minimap canvas.
+    #[must_use]
+    pub fn mini_map_padding(&self) -> ImVec2 {
+        self.0.MiniMapPadding
+    }
+
+    /// Sets the padding inside the minimap canvas.
+    pub fn set_mini_map_padding(&mut self, value: ImVec2) -> &mut Self {
+        self.0.MiniMapPadding = value;
+        self
+    }
+
+    /// Offset of the minimap from its corner.
+    #[must_use]
+    pub fn mini_map_offset(&self) -> ImVec2 {
+        self.0.MiniMapOffset
+    }
+
+    /// Sets the offset of the minimap from its corner.
+    pub fn set_mini_map_offset(&mut self, value: ImVec2) -> &mut Self {
+        self.0.MiniMapOffset = value;
+        self
+    }
+
+    /// Multiplies all sizes, paddings and offsets by `factor`, like imgui's `ScaleAllSizes`.
+    ///
+    /// Use [`crate::Context::set_style_scale_factor`] to follow changes of the scale factor
+    /// without scaling the style repeatedly.
+    #[doc(alias = "ScaleAllSizes")]
+    pub fn scale_all_sizes(&mut self, factor: f32) -> &mut Self {
+        let scale = |value: ImVec2| ImVec2 {
+            x: value.x * factor,
+            y: value.y * factor,
+        };
+        let style = &mut self.0;
+        style.GridSpacing *= factor;
+        style.NodeCornerRounding *= factor;
+        style.NodePadding = scale(style.NodePadding);
+        style.NodeBorderThickness *= factor;
+        style.LinkThickness *= factor;
+        style.LinkHoverDistance *= factor;
+        style.PinCircleRadius *= factor;
+        style.PinQuadSideLength *= factor;
+        style.PinTriangleSideLength *= factor;
+        style.PinLineThickness *= factor;
+        style.PinHoverRadius *= factor;
+        style.PinOffset *= factor;
+        style.MiniMapPadding = scale(style.MiniMapPadding);
+        style.MiniMapOffset = scale(style.MiniMapOffset);
+        self
+    }
+
+    /// Returns the color used for the given `ColorStyle` item.
+    ///
+    /// Panics for [`ColorStyle::COUNT`].
+    #[must_use]
+    pub fn color(&self, item: ColorStyle) -> ImColor32 {
+        ImColor32::from_bits(self.0.Colors[item as usize])
+    }
+
+    /// Sets the color used for the given `ColorStyle` item.
+    ///
+    /// Panics for [`ColorStyle::COUNT`].
+    pub fn set_color<C: Into<ImColor32>>(&mut self, item: ColorStyle, color: C) -> &mut Self {
+        self.0.Colors[item as usize] = color.into().to_bits();
+        self
+    }
+
+    /// Returns the enabled style flags.
+    #[must_use]
+    pub fn flags(&self) -> StyleFlags {
+        StyleFlags::from_bits_retain(self.0.Flags)
+    }
+
+    /// Replaces the enabled style flags.
+    ///
+    /// ```no_run
+    /// # let mut style = imnodes::Style::default();
+    /// let mut flags = style.flags();
+    /// flags.insert(imnodes::StyleFlags::GRID_SNAPPING);
+    /// flags.remove(imnodes::StyleFlags::NODE_OUTLINE);
+    /// style.set_flags(flags).set_grid_spacing(32.0);
+    /// ```
+    pub fn set_flags(&mut self, flags: StyleFlags) -> &mut Self {
+        self.0.Flags = flags.bits();
+        self
+    }
+}
+
+/// Deriving the colors from an imgui theme.
+impl Style {
+    /// Creates a style with the default sizes and colors matching the given imgui style.
+    ///
+    /// See [`Style::set_colors_from_imgui_style`] for how the colors are picked.
+    ///
+    /// ```no_run
+    /// # let mut imgui: imgui::Context = unimplemented!();
+    /// imgui.style_mut().use_light_colors();
+    /// let style = imnodes::Style::from_imgui_style(imgui.style());
+    /// ```
+    #[must_use]
+    pub fn from_imgui_style(imgui_style: &imgui::Style) -> Self {
+        let mut style = Self::default();
+        let _ = style.set_colors_from_imgui_style(imgui_style);
+        style
+    }
+
+    /// Replaces all colors by colors of the given imgui style, keeping the sizes.
+    ///
+    /// Nodes use the popup and frame colors, title bars the title and header colors, links the
+    /// button colors and pins the slider grab colors. The grid is drawn on the window background
+    /// with lines in a translucent text color, so it contrasts with any theme.
+    pub fn set_colors_from_imgui_style(&mut self, imgui_style: &imgui::Style) -> &mut Self {
+        use imgui::StyleColor;
+
+        let color = |item: StyleColor| imgui_style[item];
+        let translucent = |item: StyleColor, alpha: f32| {
+            let [r, g, b, a] = imgui_style[item];
+            [r, g, b, a * alpha]
+        };
+        // Typed with the length of the color array, so no slot can be forgotten.
+        let colors: [_; ColorStyle::COUNT as usize] = [
+            (ColorStyle::NodeBackground, color(StyleColor::PopupBg)),
+            (
+                ColorStyle::NodeBackgroundHovered,
+                color(StyleColor::FrameBgHovered),
+            ),
+            (
+                ColorStyle::NodeBackgroundSelected,
+                color(StyleColor::FrameBgActive),
+            ),
+            (ColorStyle::NodeOutline, color(StyleColor::Border)),
+            (ColorStyle::TitleBar, color(StyleColor::TitleBgActive)),
+            (
+                ColorStyle::TitleBarHovered,
+                color(StyleColor::HeaderHovered),
+            ),
+            (
+                ColorStyle::TitleBarSelected,
+                color(StyleColor::HeaderActive),
+            ),
+            (ColorStyle::Link, color(StyleColor::Button)),
+            (ColorStyle::LinkHovered, color(StyleColor::ButtonHovered)),
+            (ColorStyle::LinkSelected, color(StyleColor::ButtonActive)),
+            (ColorStyle::Pin, color(StyleColor::SliderGrab)),
+            (ColorStyle::PinHovered, color(StyleColor::SliderGrabActive)),
+            (ColorStyle::BoxSelector, color(StyleColor::TextSelectedBg)),
+            (
+                ColorStyle::BoxSelectorOutline,
+                color(StyleColor::NavHighlight),
+            ),
+            (ColorStyle::GridBackground, color(StyleColor::WindowBg)),
+            (ColorStyle::GridLine, translucent(StyleColor::Text, 0.16)),
+            (
+                ColorStyle::GridLinePrimary,
+                translucent(StyleColor::Text, 0.24),
+            ),
+            (
+                ColorStyle::MiniMapBackground,
+                translucent(StyleColor::PopupBg, 0.6),
+            ),
+            (
+                ColorStyle::MiniMapBackgroundHovered,
+                color(StyleColor::PopupBg),
+            ),
+            (ColorStyle::MiniMapOutline, color(StyleColor::Border)),
+            (
+                ColorStyle::MiniMapOutlineHovered,
+                color(StyleColor::SeparatorHovered),
+            ),
+            (
+                ColorStyle::MiniMapNodeBackground,
+                color(StyleColor::FrameBg),
+            ),
+            (
+                ColorStyle::MiniMapNodeBackgroundHovered,
+                color(StyleColor::FrameBgHovered),
+            ),
+            (
+                ColorStyle::MiniMapNodeBackgroundSelected,
+                color(StyleColor::FrameBgActive),
+            ),
+            (ColorStyle::MiniMapNodeOutline, color(StyleColor::Border)),
+            (ColorStyle::MiniMapLink, color(StyleColor::Button)),
+            (
+                ColorStyle::MiniMapLinkSelected,
+                color(StyleColor::ButtonActive),
+            ),
+            (
+                ColorStyle::MiniMapCanvas,
+                translucent(StyleColor::Text, 0.1),
+            ),
+            (
+                ColorStyle::MiniMapCanvasOutline,
+                translucent(StyleColor::Text, 0.8),
+            ),
+        ];
+        for (item, [r, g, b, a]) in colors {
+            let _ = self.set_color(item, ImColor32::from_rgba_f32s(r, g, b, a));
+        }
+        self
+    }
+}
+
 #[deprecated = "Use `imnodes::Style::default()` instead."]
 #[must_use]
 /// Creates an `ImNodesStyle` struct initialized with default values and the dark color theme.
@@ -90,12 +481,51 @@ impl EditorContext {
         unsafe { sys::imnodes_StyleColorsLight(&mut style.0) };
         self
     }
+
+    /// Gives this editor its own style, which [`crate::editor()`] uses instead of the shared
+    /// style of [`EditorContext::get_style`] while drawing this editor.
+    ///
+    /// The style is stored unscaled and scaled by [`crate::Context::style_scale_factor`]
+    /// while drawing.
+    ///
+    /// Colors and style variables pushed before calling [`crate::editor()`] modify the shared
+    /// style and are therefore hidden by the own style. Push them inside the editor, e.g. with
+    /// [`EditorScope::with_color`], or change the own style instead.
+    ///
+    /// ```no_run
+    /// # let mut editor_context: imnodes::EditorContext = unimplemented!();
+    /// let mut style = imnodes::Style::default();
+    /// let _ = editor_context.set_style_colors_light(&mut style);
+    /// let _ = editor_context.set_own_style(style);
+    /// ```
+    pub fn set_own_style(&mut self, style: Style) -> &mut Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Returns the style owned by this editor, if any.
+    #[must_use]
+    pub fn own_style(&self) -> Option<&Style> {
+        self.style.as_ref()
+    }
+
+    /// Returns the style owned by this editor to modify it, if any.
+    #[must_use]
+    pub fn own_style_mut(&mut self) -> Option<&mut Style> {
+        self.style.as_mut()
+    }
+
+    /// Makes this editor use the shared style again and returns the style it owned.
+    pub fn clear_own_style(&mut self) -> Option<Style> {
+        self.style.take()
+    }
 }
 
 /// Identifies a specific color setting within the node editor's style.
 ///
 /// Used with [`ColorStyle::push_color`] and [`ColorToken::pop`].
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u32)]
 pub enum ColorStyle {
     /// Node background color.
@@ -157,6 +587,7 @@ pub enum ColorStyle {
     /// Minimap canvas outline color.
     MiniMapCanvasOutline = sys::ImNodesCol__ImNodesCol_MiniMapCanvasOutline,
     /// Total number of color styles.
+    #[cfg_attr(feature = "serde", serde(skip))]
     COUNT = sys::ImNodesCol__ImNodesCol_COUNT,
 }
 
@@ -164,6 +595,39 @@ impl ColorStyle {
     /// The total number of distinct color style settings.
     pub const COUNT: u32 = sys::ImNodesCol__ImNodesCol_COUNT;
 
+    /// All color style settings, in the order of the style's color array.
+    pub const ALL: [Self; Self::COUNT as usize] = [
+        Self::NodeBackground,
+        Self::NodeBackgroundHovered,
+        Self::NodeBackgroundSelected,
+        Self::NodeOutline,
+        Self::TitleBar,
+        Self::TitleBarHovered,
+        Self::TitleBarSelected,
+        Self::Link,
+        Self::LinkHovered,
+        Self::LinkSelected,
+        Self::Pin,
+        Self::PinHovered,
+        Self::BoxSelector,
+        Self::BoxSelectorOutline,
+        Self::GridBackground,
+        Self::GridLine,
+        Self::GridLinePrimary,
+        Self::MiniMapBackground,
+        Self::MiniMapBackgroundHovered,
+        Self::MiniMapOutline,
+        Self::MiniMapOutlineHovered,
+        Self::MiniMapNodeBackground,
+        Self::MiniMapNodeBackgroundHovered,
+        Self::MiniMapNodeBackgroundSelected,
+        Self::MiniMapNodeOutline,
+        Self::MiniMapLink,
+        Self::MiniMapLinkSelected,
+        Self::MiniMapCanvas,
+        Self::MiniMapCanvasOutline,
+    ];
+
     /// Pushes a color onto the style stack for this specific `ColorStyle` item.
     ///
     /// The change applies until the returned [`ColorToken`] is popped.
@@ -176,13 +640,43 @@ impl ColorStyle {
         unsafe { sys::imnodes_PushColorStyle(self as i32, color.into()) };
         ColorToken { ended: false }
     }
+
+    /// Pushes a color for this `ColorStyle` item while running `f`.
+    ///
+    /// The color is popped again when `f` returns or panics. `f` gets the context back,
+    /// e.g. to draw the editor with it. See [`crate::EditorScope::with_color`] to style
+    /// single nodes inside the editor.
+    ///
+    /// ```no_run
+    /// # let mut editor_context: imnodes::EditorContext = unimplemented!();
+    /// let title_bar = imnodes::ColorStyle::TitleBar;
+    /// let outer_scope = title_bar.with_color([0.8, 0.2, 0.2], &mut editor_context, |context| {
+    ///     imnodes::editor(context, |editor_scope| {
+    ///         // nodes submitted here have a red title bar
+    ///     })
+    /// });
+    /// ```
+    #[doc(alias = "PushColorStyle", alias = "PopColorStyle")]
+    pub fn with_color<C: Into<ImColor32>, R>(
+        self,
+        color: C,
+        context: &mut EditorContext,
+        f: impl FnOnce(&mut EditorContext) -> R,
+    ) -> R {
+        let token = self.push_color(color, context);
+        let result = f(context);
+        token.pop();
+        result
+    }
 }
 
 /// A token representing a pushed color style change.
 ///
 /// Must be popped using [`ColorToken::pop`] before it goes out of scope to maintain
-/// the integrity of the style stack. Dropping without popping will cause a panic.
-#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+/// the integrity of the style stack. Dropping without popping will cause a panic,
+/// unless [`set_auto_pop_tokens`] is enabled. Prefer [`ColorStyle::with_color`].
+// Not `Clone`: every token pops when dropped, so a clone would pop the stack twice.
+#[derive(Debug)]
 pub struct ColorToken {
     ended: bool,
 }
@@ -199,12 +693,16 @@ impl ColorToken {
 }
 
 impl Drop for ColorToken {
-    /// Panics if the token is dropped without being popped.
+    /// Panics if the token is dropped without being popped, see [`set_auto_pop_tokens`].
     fn drop(&mut self) {
-        assert!(
-            self.ended,
-            "`ColorToken` was dropped without calling `pop()`. This likely means a color style was pushed but not popped, leading to an incorrect style stack."
-        );
+        if !self.ended {
+            unpopped_token(
+                "ColorToken",
+                "This likely means a color style was pushed but not popped, leading to an incorrect style stack.",
+            );
+            // Safety: C API call. Pops the color pushed for this token.
+            unsafe { sys::imnodes_PopColorStyle() };
+        }
     }
 }
 
@@ -226,6 +724,8 @@ pub enum MiniMapLocation {
 /// Identifies a specific style variable setting within the node editor's style.
 ///
 /// Use [`StyleVar::push_f32`] or [`StyleVar::push_vec2`] to modify these temporarily.
+/// [`StyleVarF32`] and [`StyleVarVec2`] split the variables by value type, so passing the
+/// wrong kind of value fails to compile.
 /// The default size of each pin shape is balanced to occupy approximately the same surface area on the screen.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 #[repr(u32)]
@@ -270,18 +770,13 @@ impl StyleVar {
     /// The change applies until the returned [`StyleVarToken`] is popped.
     /// Remember to call `.pop(1)` on the token before it goes out of scope.
     /// Panics if this `StyleVar` expects an `ImVec2`.
+    /// Use [`StyleVarF32::push`] to have this checked at compile time.
     #[doc(alias = "PushStyleVar_Float")]
     #[must_use = "The returned StyleVarToken must be popped to restore the previous value"]
-    pub fn push_f32(self, value: f32, _context: &EditorContext) -> StyleVarToken {
-        match self {
-            StyleVar::NodePadding | StyleVar::MiniMapPadding | StyleVar::MiniMapOffset => {
-                panic!("StyleVar {self:?} expects an ImVec2, use push_vec2");
-            }
-            _ => {}
-        }
-        // Safety: C API call. Pushes a float onto the internal stack.
-        unsafe { sys::imnodes_PushStyleVar_Float(self as i32, value) };
-        StyleVarToken { ended: false }
+    pub fn push_f32(self, value: f32, context: &EditorContext) -> StyleVarToken {
+        StyleVarF32::try_from(self)
+            .unwrap_or_else(|_| panic!("StyleVar {self:?} expects an ImVec2, use push_vec2"))
+            .push(value, context)
     }
 
     /// Pushes an `ImVec2` value onto the style stack for this specific `StyleVar` item.
@@ -289,26 +784,228 @@ impl StyleVar {
     /// The change applies until the returned [`StyleVarToken`] is popped.
     /// Remember to call `.pop(1)` on the token before it goes out of scope.
     /// Panics if this `StyleVar` expects a float.
+    /// Use [`StyleVarVec2::push`] to have this checked at compile time.
     #[doc(alias = "PushStyleVar_Vec2")]
     #[must_use = "The returned StyleVarToken must be popped to restore the previous value"]
-    pub fn push_vec2(self, value: sys::ImVec2, _context: &EditorContext) -> StyleVarToken {
-        match self {
-            StyleVar::NodePadding | StyleVar::MiniMapPadding | StyleVar::MiniMapOffset => {}
-            _ => {
-                panic!("StyleVar {self:?} expects a float, use push_f32");
-            }
+    pub fn push_vec2(self, value: sys::ImVec2, context: &EditorContext) -> StyleVarToken {
+        StyleVarVec2::try_from(self)
+            .unwrap_or_else(|_| panic!("StyleVar {self:?} expects a float, use push_f32"))
+            .push(value, context)
+    }
+
+    /// Pushes a float value for this `StyleVar` item while running `f`.
+    ///
+    /// The value is popped again when `f` returns or panics. `f` gets the context back.
+    /// Panics if this `StyleVar` expects an `ImVec2`.
+    #[doc(alias = "PushStyleVar_Float", alias = "PopStyleVar")]
+    pub fn with_f32<R>(
+        self,
+        value: f32,
+        context: &mut EditorContext,
+        f: impl FnOnce(&mut EditorContext) -> R,
+    ) -> R {
+        let token = self.push_f32(value, context);
+        let result = f(context);
+        token.pop(1);
+        result
+    }
+
+    /// Pushes an `ImVec2` value for this `StyleVar` item while running `f`.
+    ///
+    /// The value is popped again when `f` returns or panics. `f` gets the context back.
+    /// Panics if this `StyleVar` expects a float.
+    #[doc(alias = "PushStyleVar_Vec2", alias = "PopStyleVar")]
+    pub fn with_vec2<R>(
+        self,
+        value: sys::ImVec2,
+        context: &mut EditorContext,
+        f: impl FnOnce(&mut EditorContext) -> R,
+    ) -> R {
+        let token = self.push_vec2(value, context);
+        let result = f(context);
+        token.pop(1);
+        result
+    }
+}
+
+/// A style variable holding a float, the subset of [`StyleVar`] accepted by
+/// [`StyleVar::push_f32`].
+///
+/// Converts from and into [`StyleVar`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[repr(u32)]
+pub enum StyleVarF32 {
+    /// Spacing between grid lines.
+    GridSpacing = sys::ImNodesStyleVar__ImNodesStyleVar_GridSpacing,
+    /// Corner rounding radius for nodes.
+    NodeCornerRounding = sys::ImNodesStyleVar__ImNodesStyleVar_NodeCornerRounding,
+    /// Thickness of node borders.
+    NodeBorderThickness = sys::ImNodesStyleVar__ImNodesStyleVar_NodeBorderThickness,
+    /// Thickness of links between pins.
+    LinkThickness = sys::ImNodesStyleVar__ImNodesStyleVar_LinkThickness,
+    /// Number of line segments used to render links per unit of length.
+    LinkLineSegmentsPerLength = sys::ImNodesStyleVar__ImNodesStyleVar_LinkLineSegmentsPerLength,
+    /// Distance threshold for detecting link hovering.
+    LinkHoverDistance = sys::ImNodesStyleVar__ImNodesStyleVar_LinkHoverDistance,
+    /// The circle radius of circle pins.
+    PinCircleRadius = sys::ImNodesStyleVar__ImNodesStyleVar_PinCircleRadius,
+    /// The quad side length of quad pins.
+    PinQuadSideLength = sys::ImNodesStyleVar__ImNodesStyleVar_PinQuadSideLength,
+    /// The equilateral triangle side length of triangle pins.
+    PinTriangleSideLength = sys::ImNodesStyleVar__ImNodesStyleVar_PinTriangleSideLength,
+    /// The thickness of the line of pins which are not filled.
+    PinLineThickness = sys::ImNodesStyleVar__ImNodesStyleVar_PinLineThickness,
+    /// The radius around the pin's center within which it is hovered.
+    PinHoverRadius = sys::ImNodesStyleVar__ImNodesStyleVar_PinHoverRadius,
+    /// Horizontal offset of the pins from the edge of the node.
+    PinOffset = sys::ImNodesStyleVar__ImNodesStyleVar_PinOffset,
+}
+
+impl StyleVarF32 {
+    /// Pushes a value onto the style stack for this style variable.
+    ///
+    /// The change applies until the returned [`StyleVarToken`] is popped.
+    #[doc(alias = "PushStyleVar_Float")]
+    #[must_use = "The returned StyleVarToken must be popped to restore the previous value"]
+    pub fn push(self, value: f32, _context: &EditorContext) -> StyleVarToken {
+        // Safety: C API call. Pushes a float onto the internal stack.
+        unsafe { sys::imnodes_PushStyleVar_Float(self as i32, value) };
+        StyleVarToken { ended: false }
+    }
+
+    /// Pushes a value for this style variable while running `f`, see [`StyleVar::with_f32`].
+    #[doc(alias = "PushStyleVar_Float", alias = "PopStyleVar")]
+    pub fn with<R>(
+        self,
+        value: f32,
+        context: &mut EditorContext,
+        f: impl FnOnce(&mut EditorContext) -> R,
+    ) -> R {
+        let token = self.push(value, context);
+        let result = f(context);
+        token.pop(1);
+        result
+    }
+}
+
+impl From<StyleVarF32> for StyleVar {
+    fn from(style_var: StyleVarF32) -> Self {
+        match style_var {
+            StyleVarF32::GridSpacing => Self::GridSpacing,
+            StyleVarF32::NodeCornerRounding => Self::NodeCornerRounding,
+            StyleVarF32::NodeBorderThickness => Self::NodeBorderThickness,
+            StyleVarF32::LinkThickness => Self::LinkThickness,
+            StyleVarF32::LinkLineSegmentsPerLength => Self::LinkLineSegmentsPerLength,
+            StyleVarF32::LinkHoverDistance => Self::LinkHoverDistance,
+            StyleVarF32::PinCircleRadius => Self::PinCircleRadius,
+            StyleVarF32::PinQuadSideLength => Self::PinQuadSideLength,
+            StyleVarF32::PinTriangleSideLength => Self::PinTriangleSideLength,
+            StyleVarF32::PinLineThickness => Self::PinLineThickness,
+            StyleVarF32::PinHoverRadius => Self::PinHoverRadius,
+            StyleVarF32::PinOffset => Self::PinOffset,
         }
+    }
+}
+
+impl TryFrom<StyleVar> for StyleVarF32 {
+    /// The style variable, which does not hold a float.
+    type Error = StyleVar;
+
+    fn try_from(style_var: StyleVar) -> Result<Self, Self::Error> {
+        Ok(match style_var {
+            StyleVar::GridSpacing => Self::GridSpacing,
+            StyleVar::NodeCornerRounding => Self::NodeCornerRounding,
+            StyleVar::NodeBorderThickness => Self::NodeBorderThickness,
+            StyleVar::LinkThickness => Self::LinkThickness,
+            StyleVar::LinkLineSegmentsPerLength => Self::LinkLineSegmentsPerLength,
+            StyleVar::LinkHoverDistance => Self::LinkHoverDistance,
+            StyleVar::PinCircleRadius => Self::PinCircleRadius,
+            StyleVar::PinQuadSideLength => Self::PinQuadSideLength,
+            StyleVar::PinTriangleSideLength => Self::PinTriangleSideLength,
+            StyleVar::PinLineThickness => Self::PinLineThickness,
+            StyleVar::PinHoverRadius => Self::PinHoverRadius,
+            StyleVar::PinOffset => Self::PinOffset,
+            StyleVar::NodePadding
+            | StyleVar::MiniMapPadding
+            | StyleVar::MiniMapOffset
+            | StyleVar::COUNT => return Err(style_var),
+        })
+    }
+}
+
+/// A style variable holding an `ImVec2`, the subset of [`StyleVar`] accepted by
+/// [`StyleVar::push_vec2`].
+///
+/// Converts from and into [`StyleVar`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[repr(u32)]
+pub enum StyleVarVec2 {
+    /// Padding inside nodes.
+    NodePadding = sys::ImNodesStyleVar__ImNodesStyleVar_NodePadding,
+    /// Padding inside the minimap canvas.
+    MiniMapPadding = sys::ImNodesStyleVar__ImNodesStyleVar_MiniMapPadding,
+    /// Offset of the minimap from its corner.
+    MiniMapOffset = sys::ImNodesStyleVar__ImNodesStyleVar_MiniMapOffset,
+}
+
+impl StyleVarVec2 {
+    /// Pushes a value onto the style stack for this style variable.
+    ///
+    /// The change applies until the returned [`StyleVarToken`] is popped.
+    #[doc(alias = "PushStyleVar_Vec2")]
+    #[must_use = "The returned StyleVarToken must be popped to restore the previous value"]
+    pub fn push(self, value: sys::ImVec2, _context: &EditorContext) -> StyleVarToken {
         // Safety: C API call. Pushes an ImVec2 onto the internal stack.
         unsafe { sys::imnodes_PushStyleVar_Vec2(self as i32, value) };
         StyleVarToken { ended: false }
     }
+
+    /// Pushes a value for this style variable while running `f`, see [`StyleVar::with_vec2`].
+    #[doc(alias = "PushStyleVar_Vec2", alias = "PopStyleVar")]
+    pub fn with<R>(
+        self,
+        value: sys::ImVec2,
+        context: &mut EditorContext,
+        f: impl FnOnce(&mut EditorContext) -> R,
+    ) -> R {
+        let token = self.push(value, context);
+        let result = f(context);
+        token.pop(1);
+        result
+    }
+}
+
+impl From<StyleVarVec2> for StyleVar {
+    fn from(style_var: StyleVarVec2) -> Self {
+        match style_var {
+            StyleVarVec2::NodePadding => Self::NodePadding,
+            StyleVarVec2::MiniMapPadding => Self::MiniMapPadding,
+            StyleVarVec2::MiniMapOffset => Self::MiniMapOffset,
+        }
+    }
+}
+
+impl TryFrom<StyleVar> for StyleVarVec2 {
+    /// The style variable, which does not hold an `ImVec2`.
+    type Error = StyleVar;
+
+    fn try_from(style_var: StyleVar) -> Result<Self, Self::Error> {
+        match style_var {
+            StyleVar::NodePadding => Ok(Self::NodePadding),
+            StyleVar::MiniMapPadding => Ok(Self::MiniMapPadding),
+            StyleVar::MiniMapOffset => Ok(Self::MiniMapOffset),
+            _ => Err(style_var),
+        }
+    }
 }
 
 /// A token representing a pushed style variable change (float or ImVec2).
 ///
 /// Must be popped using [`StyleVarToken::pop`] before it goes out of scope to maintain
-/// the integrity of the style stack. Dropping without popping will cause a panic.
-#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+/// the integrity of the style stack. Dropping without popping will cause a panic,
+/// unless [`set_auto_pop_tokens`] is enabled. Prefer [`StyleVar::with_f32`] and
+/// [`StyleVar::with_vec2`].
+#[derive(Debug)]
 pub struct StyleVarToken {
     ended: bool,
 }
@@ -328,31 +1025,56 @@ impl StyleVarToken {
 }
 
 impl Drop for StyleVarToken {
-    /// Panics if the token is dropped without being popped.
+    /// Panics if the token is dropped without being popped, see [`set_auto_pop_tokens`].
     fn drop(&mut self) {
-        assert!(
-            self.ended,
-            "`StyleVarToken` was dropped without calling `pop()`. This likely means a style variable was pushed but not popped, leading to an incorrect style stack."
-        );
+        if !self.ended {
+            unpopped_token(
+                "StyleVarToken",
+                "This likely means a style variable was pushed but not popped, leading to an incorrect style stack.",
+            );
+            // Safety: C API call. Pops the style variable pushed for this token.
+            unsafe { sys::imnodes_PopStyleVar(1) };
+        }
     }
 }
 
-/// Flags controlling boolean style options for the editor.
-///
-/// These flags are set in [`Style.0.Flags`]. Multiple flags can be combined using bitwise OR.
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
-#[repr(i32)] // Match the underlying C type ImNodesStyleFlags
-pub enum StyleFlags {
+bitflags::bitflags! {
+    /// Flags controlling boolean style options for the editor.
+    ///
+    /// Read and set with [`Style::flags`] and [`Style::set_flags`].
+    /// Flags are combined with `|` or [`StyleFlags::insert`].
+    #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
+    pub struct StyleFlags: i32 { // Match the underlying C type ImNodesStyleFlags
+        /// Draw outlines around nodes.
+        const NODE_OUTLINE = sys::ImNodesStyleFlags__ImNodesStyleFlags_NodeOutline as i32;
+        /// Draw grid lines in the background.
+        const GRID_LINES = sys::ImNodesStyleFlags__ImNodesStyleFlags_GridLines as i32;
+        /// Draw primary grid lines (at multiples of `GridSpacing * 10.0`) thicker.
+        const GRID_LINES_PRIMARY = sys::ImNodesStyleFlags__ImNodesStyleFlags_GridLinesPrimary as i32;
+        /// Enable snapping nodes to the grid when dragging. Requires [`StyleFlags::GRID_LINES`].
+        const GRID_SNAPPING = sys::ImNodesStyleFlags__ImNodesStyleFlags_GridSnapping as i32;
+    }
+}
+
+/// The names of the former `StyleFlags` enum variants.
+#[allow(non_upper_case_globals)]
+impl StyleFlags {
     /// No flags set.
-    None = sys::ImNodesStyleFlags__ImNodesStyleFlags_None as i32,
+    #[deprecated = "Use `StyleFlags::empty()` instead."]
+    pub const None: Self = Self::empty();
     /// Draw outlines around nodes.
-    NodeOutline = sys::ImNodesStyleFlags__ImNodesStyleFlags_NodeOutline as i32,
+    #[deprecated = "Use `StyleFlags::NODE_OUTLINE` instead."]
+    pub const NodeOutline: Self = Self::NODE_OUTLINE;
     /// Draw grid lines in the background.
-    GridLines = sys::ImNodesStyleFlags__ImNodesStyleFlags_GridLines as i32,
+    #[deprecated = "Use `StyleFlags::GRID_LINES` instead."]
+    pub const GridLines: Self = Self::GRID_LINES;
     /// Draw primary grid lines (at multiples of `GridSpacing * 10.0`) thicker.
-    GridLinesPrimary = sys::ImNodesStyleFlags__ImNodesStyleFlags_GridLinesPrimary as i32,
-    /// Enable snapping nodes to the grid when dragging. Requires [`StyleFlags::GridLines`].
-    GridSnapping = sys::ImNodesStyleFlags__ImNodesStyleFlags_GridSnapping as i32,
+    #[deprecated = "Use `StyleFlags::GRID_LINES_PRIMARY` instead."]
+    pub const GridLinesPrimary: Self = Self::GRID_LINES_PRIMARY;
+    /// Enable snapping nodes to the grid when dragging.
+    #[deprecated = "Use `StyleFlags::GRID_SNAPPING` instead."]
+    pub const GridSnapping: Self = Self::GRID_SNAPPING;
 }
 
 /// Controls the visual shape of attribute pins.
@@ -411,13 +1133,108 @@ impl EditorContext {
         unsafe { sys::imnodes_PushAttributeFlag(flag as i32) };
         AttributeFlagToken { ended: false }
     }
+
+    /// Pushes an [`AttributeFlags`] setting while running `f`.
+    ///
+    /// The flag applies to the attributes created in `f` and is popped again when `f`
+    /// returns or panics. `f` gets the context back, e.g. to draw the editor with it.
+    ///
+    /// ```no_run
+    /// # let mut editor_context: imnodes::EditorContext = unimplemented!();
+    /// let flag = imnodes::AttributeFlags::EnableLinkDetachWithDragClick;
+    /// let outer_scope = editor_context.with_attribute_flag(flag, |context| {
+    ///     imnodes::editor(context, |editor_scope| {
+    ///         // links can be detached from all pins submitted here
+    ///     })
+    /// });
+    /// ```
+    #[doc(alias = "PushAttributeFlag", alias = "PopAttributeFlag")]
+    pub fn with_attribute_flag<R>(
+        &mut self,
+        flag: AttributeFlags,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        let token = self.push_attribute_flag(flag);
+        let result = f(self);
+        token.pop();
+        result
+    }
+}
+
+/// Scoped style changes for the nodes, links and pins submitted inside the editor.
+impl EditorScope<'_, '_> {
+    /// Pushes a color while running `f`, see [`ColorStyle::with_color`].
+    ///
+    /// ```no_run
+    /// # let mut editor_context: imnodes::EditorContext = unimplemented!();
+    /// # let id: imnodes::NodeId = unimplemented!();
+    /// imnodes::editor(&mut editor_context, |mut editor_scope| {
+    ///     editor_scope.with_color(imnodes::ColorStyle::TitleBar, [0.8, 0.2, 0.2], |scope| {
+    ///         scope.add_node(id, |_node_scope| {});
+    ///     });
+    /// });
+    /// ```
+    #[doc(alias = "PushColorStyle", alias = "PopColorStyle")]
+    pub fn with_color<C: Into<ImColor32>, R>(
+        &mut self,
+        style: ColorStyle,
+        color: C,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        let token = style.push_color(color, self.context);
+        let result = f(self);
+        token.pop();
+        result
+    }
+
+    /// Pushes a float style variable while running `f`, see [`StyleVarF32::with`].
+    #[doc(alias = "PushStyleVar_Float", alias = "PopStyleVar")]
+    pub fn with_style_var_f32<R>(
+        &mut self,
+        style_var: StyleVarF32,
+        value: f32,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        let token = style_var.push(value, self.context);
+        let result = f(self);
+        token.pop(1);
+        result
+    }
+
+    /// Pushes an `ImVec2` style variable while running `f`, see [`StyleVarVec2::with`].
+    #[doc(alias = "PushStyleVar_Vec2", alias = "PopStyleVar")]
+    pub fn with_style_var_vec2<R>(
+        &mut self,
+        style_var: StyleVarVec2,
+        value: sys::ImVec2,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        let token = style_var.push(value, self.context);
+        let result = f(self);
+        token.pop(1);
+        result
+    }
+
+    /// Pushes an attribute flag while running `f`, see [`EditorContext::with_attribute_flag`].
+    #[doc(alias = "PushAttributeFlag", alias = "PopAttributeFlag")]
+    pub fn with_attribute_flag<R>(
+        &mut self,
+        flag: AttributeFlags,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        let token = self.context.push_attribute_flag(flag);
+        let result = f(self);
+        token.pop();
+        result
+    }
 }
 
 /// A token representing a pushed attribute flag change.
 ///
 /// Must be popped using [`AttributeFlagToken::pop`] before it goes out of scope to maintain
-/// the integrity of the attribute flag stack. Dropping without popping will cause a panic.
-#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+/// the integrity of the attribute flag stack. Dropping without popping will cause a panic,
+/// unless [`set_auto_pop_tokens`] is enabled. Prefer [`EditorContext::with_attribute_flag`].
+#[derive(Debug)]
 pub struct AttributeFlagToken {
     ended: bool,
 }
@@ -433,11 +1250,15 @@ impl AttributeFlagToken {
 }
 
 impl Drop for AttributeFlagToken {
-    /// Panics if the token is dropped without being popped.
+    /// Panics if the token is dropped without being popped, see [`set_auto_pop_tokens`].
     fn drop(&mut self) {
-        assert!(
-            self.ended,
-            "`AttributeFlagToken` was dropped without calling `pop()`. This likely means an attribute flag was pushed but not popped, leading to incorrect behavior."
-        );
+        if !self.ended {
+            unpopped_token(
+                "AttributeFlagToken",
+                "This likely means an attribute flag was pushed but not popped, leading to incorrect behavior.",
+            );
+            // Safety: C API call. Pops the attribute flag pushed for this token.
+            unsafe { sys::imnodes_PopAttributeFlag() };
+        }
     }
 }
@@ -0,0 +1,447 @@
+/*!
+Algorithms over the nodes of an editor and the links between them.
+
+[`Topology`] is built from node IDs and anything implementing [`Edge`], e.g. the [`Link`]s
+reported by [`crate::OuterScope::links_created`], `(NodeId, NodeId)` pairs of an application's
+own graph, or the links of a [`crate::graph::Graph`] when the `graph` feature is enabled.
+Results keep the order the nodes were passed in wherever the algorithm leaves a choice.
+*/
+
+use crate::{Link, NodeId};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+/// A directed connection from one node to another.
+pub trait Edge {
+    /// Returns the node the edge starts at, the node of the output pin of a link.
+    fn start_node(&self) -> NodeId;
+    /// Returns the node the edge ends at, the node of the input pin of a link.
+    fn end_node(&self) -> NodeId;
+}
+
+impl Edge for Link {
+    fn start_node(&self) -> NodeId {
+        self.start_node
+    }
+
+    fn end_node(&self) -> NodeId {
+        self.end_node
+    }
+}
+
+impl Edge for (NodeId, NodeId) {
+    fn start_node(&self) -> NodeId {
+        self.0
+    }
+
+    fn end_node(&self) -> NodeId {
+        self.1
+    }
+}
+
+impl<E: Edge + ?Sized> Edge for &E {
+    fn start_node(&self) -> NodeId {
+        (**self).start_node()
+    }
+
+    fn end_node(&self) -> NodeId {
+        (**self).end_node()
+    }
+}
+
+/// The nodes and edges of a graph, prepared for the algorithms below.
+///
+/// ```no_run
+/// # let nodes: Vec<imnodes::NodeId> = unimplemented!();
+/// # let links: Vec<imnodes::Link> = unimplemented!();
+/// let topology = imnodes::Topology::new(nodes, &links);
+/// match topology.topological_order() {
+///     Ok(order) => println!("evaluate in order {order:?}"),
+///     Err(cycle) => println!("{cycle}"),
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Topology {
+    nodes: Vec<NodeId>,
+    indices: HashMap<NodeId, usize>,
+    successors: Vec<Vec<usize>>,
+    predecessors: Vec<Vec<usize>>,
+}
+
+impl Topology {
+    /// Creates the topology of `nodes` connected by `edges`.
+    ///
+    /// Nodes only appearing in `edges` are added after `nodes`. Repeated nodes and edges are
+    /// only kept once.
+    pub fn new<E: Edge>(
+        nodes: impl IntoIterator<Item = NodeId>,
+        edges: impl IntoIterator<Item = E>,
+    ) -> Self {
+        let mut topology = Self::default();
+        for node in nodes {
+            let _ = topology.index_of(node);
+        }
+        for edge in edges {
+            let start = topology.index_of(edge.start_node());
+            let end = topology.index_of(edge.end_node());
+            if !topology.successors[start].contains(&end) {
+                topology.successors[start].push(end);
+                topology.predecessors[end].push(start);
+            }
+        }
+        topology
+    }
+
+    fn index_of(&mut self, node: NodeId) -> usize {
+        *self.indices.entry(node).or_insert_with(|| {
+            self.nodes.push(node);
+            self.successors.push(Vec::new());
+            self.predecessors.push(Vec::new());
+            self.nodes.len() - 1
+        })
+    }
+
+    fn ids(&self, indices: impl IntoIterator<Item = usize>) -> Vec<NodeId> {
+        indices.into_iter().map(|index| self.nodes[index]).collect()
+    }
+
+    /// Returns all nodes.
+    #[must_use]
+    pub fn nodes(&self) -> &[NodeId] {
+        &self.nodes
+    }
+
+    /// Returns the nodes linked directly to the inputs of `node`.
+    #[must_use]
+    pub fn predecessors(&self, node: NodeId) -> Vec<NodeId> {
+        self.indices
+            .get(&node)
+            .map(|&index| self.ids(self.predecessors[index].iter().copied()))
+            .unwrap_or_default()
+    }
+
+    /// Returns the nodes linked directly to the outputs of `node`.
+    #[must_use]
+    pub fn successors(&self, node: NodeId) -> Vec<NodeId> {
+        self.indices
+            .get(&node)
+            .map(|&index| self.ids(self.successors[index].iter().copied()))
+            .unwrap_or_default()
+    }
+
+    /// Orders the nodes so every node comes after all nodes linked to its inputs, e.g. to
+    /// evaluate a dataflow graph.
+    ///
+    /// Fails with the nodes which are part of a cycle if there is one.
+    pub fn topological_order(&self) -> Result<Vec<NodeId>, Cycle> {
+        let mut in_degrees: Vec<usize> = self.predecessors.iter().map(Vec::len).collect();
+        let mut ready: VecDeque<usize> = (0..self.nodes.len())
+            .filter(|&index| in_degrees[index] == 0)
+            .collect();
+        let mut order = Vec::with_capacity(self.nodes.len());
+        while let Some(index) = ready.pop_front() {
+            order.push(self.nodes[index]);
+            for &successor in &self.successors[index] {
+                in_degrees[successor] -= 1;
+                if in_degrees[successor] == 0 {
+                    ready.push_back(successor);
+                }
+            }
+        }
+
+        if order.len() == self.nodes.len() {
+            return Ok(order);
+        }
+        // The remaining nodes are either part of a cycle or downstream of one.
+        let nodes = self
+            .strongly_connected_indices()
+            .into_iter()
+            .filter(|component| self.is_cyclic(component))
+            .flatten();
+        Err(Cycle {
+            nodes: self.ids(nodes),
+        })
+    }
+
+    /// Returns all nodes which can be reached from the outputs of `node`, nearest first.
+    ///
+    /// `node` itself is only included if it is part of a cycle.
+    #[must_use]
+    pub fn downstream(&self, node: NodeId) -> Vec<NodeId> {
+        self.reachable(node, &self.successors)
+    }
+
+    /// Returns all nodes from which the inputs of `node` can be reached, nearest first.
+    ///
+    /// `node` itself is only included if it is part of a cycle.
+    #[must_use]
+    pub fn upstream(&self, node: NodeId) -> Vec<NodeId> {
+        self.reachable(node, &self.predecessors)
+    }
+
+    /// Breadth-first search from `node` along `neighbors`.
+    fn reachable(&self, node: NodeId, neighbors: &[Vec<usize>]) -> Vec<NodeId> {
+        let Some(&start) = self.indices.get(&node) else {
+            return Vec::new();
+        };
+        let mut visited = vec![false; self.nodes.len()];
+        let mut queue = VecDeque::from([start]);
+        let mut found = Vec::new();
+        while let Some(index) = queue.pop_front() {
+            for &neighbor in &neighbors[index] {
+                if !visited[neighbor] {
+                    visited[neighbor] = true;
+                    found.push(neighbor);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        self.ids(found)
+    }
+
+    /// Groups the nodes which are connected by links, ignoring their direction.
+    ///
+    /// Unlinked nodes form a component of their own.
+    #[must_use]
+    pub fn connected_components(&self) -> Vec<Vec<NodeId>> {
+        let mut visited = vec![false; self.nodes.len()];
+        let mut components = Vec::new();
+        for root in 0..self.nodes.len() {
+            if visited[root] {
+                continue;
+            }
+            visited[root] = true;
+            let mut members = vec![root];
+            let mut next = 0;
+            while let Some(&index) = members.get(next) {
+                next += 1;
+                for &neighbor in self.successors[index]
+                    .iter()
+                    .chain(&self.predecessors[index])
+                {
+                    if !visited[neighbor] {
+                        visited[neighbor] = true;
+                        members.push(neighbor);
+                    }
+                }
+            }
+            members.sort_unstable();
+            components.push(self.ids(members));
+        }
+        components
+    }
+
+    /// Groups the nodes which can all be reached from each other, e.g. the nodes of a cycle.
+    ///
+    /// Nodes outside of cycles form a component of their own. Components come in reverse
+    /// topological order, a component only has links to components before it.
+    #[must_use]
+    pub fn strongly_connected_components(&self) -> Vec<Vec<NodeId>> {
+        self.strongly_connected_indices()
+            .into_iter()
+            .map(|component| self.ids(component))
+            .collect()
+    }
+
+    /// Returns whether the strongly connected `component` contains a cycle.
+    fn is_cyclic(&self, component: &[usize]) -> bool {
+        match component {
+            [index] => self.successors[*index].contains(index),
+            _ => true,
+        }
+    }
+
+    /// Tarjan's algorithm, iterative so deep graphs cannot overflow the stack.
+    fn strongly_connected_indices(&self) -> Vec<Vec<usize>> {
+        const UNVISITED: usize = usize::MAX;
+        let count = self.nodes.len();
+        let mut order = vec![UNVISITED; count];
+        let mut low_link = vec![0; count];
+        let mut on_stack = vec![false; count];
+        let mut stack = Vec::new();
+        let mut next_order = 0;
+        let mut components = Vec::new();
+
+        for root in 0..count {
+            if order[root] != UNVISITED {
+                continue;
+            }
+            // Nodes being visited with the index of their next successor to visit.
+            let mut path = vec![(root, 0)];
+            order[root] = next_order;
+            low_link[root] = next_order;
+            next_order += 1;
+            stack.push(root);
+            on_stack[root] = true;
+
+            while let Some(&mut (index, ref mut next_successor)) = path.last_mut() {
+                if let Some(&successor) = self.successors[index].get(*next_successor) {
+                    *next_successor += 1;
+                    if order[successor] == UNVISITED {
+                        order[successor] = next_order;
+                        low_link[successor] = next_order;
+                        next_order += 1;
+                        stack.push(successor);
+                        on_stack[successor] = true;
+                        path.push((successor, 0));
+                    } else if on_stack[successor] {
+                        low_link[index] = low_link[index].min(order[successor]);
+                    }
+                    continue;
+                }
+
+                let _ = path.pop();
+                if let Some(&(parent, _)) = path.last() {
+                    low_link[parent] = low_link[parent].min(low_link[index]);
+                }
+                if low_link[index] == order[index] {
+                    let mut component = Vec::new();
+                    while let Some(member) = stack.pop() {
+                        on_stack[member] = false;
+                        component.push(member);
+                        if member == index {
+                            break;
+                        }
+                    }
+                    component.sort_unstable();
+                    components.push(component);
+                }
+            }
+        }
+        components
+    }
+}
+
+/// The nodes of the cycles which prevent a [`Topology::topological_order`].
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct Cycle {
+    nodes: Vec<NodeId>,
+}
+
+impl Cycle {
+    /// Returns all nodes which are part of a cycle.
+    #[must_use]
+    pub fn nodes(&self) -> &[NodeId] {
+        &self.nodes
+    }
+}
+
+impl fmt::Display for Cycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the nodes {:?} form a cycle", self.nodes)
+    }
+}
+
+impl std::error::Error for Cycle {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nodes(count: i32) -> Vec<NodeId> {
+        (0..count).map(|id| NodeId { id }).collect()
+    }
+
+    #[test]
+    fn orders_a_dag() {
+        let n = nodes(5);
+        // 3 -> 0 -> 1, 3 -> 1, 2 and 4 unlinked
+        let topology = Topology::new(n.clone(), [(n[3], n[0]), (n[0], n[1]), (n[3], n[1])]);
+        assert_eq!(
+            topology.topological_order(),
+            Ok(vec![n[2], n[3], n[4], n[0], n[1]])
+        );
+        assert_eq!(topology.predecessors(n[1]), vec![n[0], n[3]]);
+        assert_eq!(topology.successors(n[3]), vec![n[0], n[1]]);
+    }
+
+    #[test]
+    fn reports_a_cycle() {
+        let n = nodes(3);
+        let topology = Topology::new(n.clone(), [(n[0], n[1]), (n[1], n[2]), (n[2], n[0])]);
+        let cycle = topology.topological_order().unwrap_err();
+        assert_eq!(cycle.nodes(), &n[..]);
+        assert_eq!(topology.strongly_connected_components(), vec![n.clone()]);
+        // Every node of the cycle reaches itself.
+        assert_eq!(topology.downstream(n[0]), vec![n[1], n[2], n[0]]);
+    }
+
+    #[test]
+    fn reports_a_self_loop() {
+        let n = nodes(2);
+        let topology = Topology::new(n.clone(), [(n[0], n[1]), (n[1], n[1])]);
+        let cycle = topology.topological_order().unwrap_err();
+        assert_eq!(cycle.nodes(), &[n[1]]);
+        assert_eq!(topology.downstream(n[1]), vec![n[1]]);
+        assert_eq!(topology.upstream(n[0]), vec![]);
+    }
+
+    #[test]
+    fn leaves_nodes_downstream_of_a_cycle_out_of_it() {
+        let n = nodes(5);
+        // 0 -> 1 <-> 2 -> 3 -> 4
+        let edges = [
+            (n[0], n[1]),
+            (n[1], n[2]),
+            (n[2], n[1]),
+            (n[2], n[3]),
+            (n[3], n[4]),
+        ];
+        let topology = Topology::new(n.clone(), edges);
+        let cycle = topology.topological_order().unwrap_err();
+        assert_eq!(cycle.nodes(), &[n[1], n[2]]);
+        assert_eq!(
+            topology.strongly_connected_components(),
+            vec![vec![n[4]], vec![n[3]], vec![n[1], n[2]], vec![n[0]]]
+        );
+    }
+
+    #[test]
+    fn finds_reachable_nodes() {
+        let n = nodes(5);
+        // 0 -> 1 -> 3, 0 -> 2 -> 3, 4 unlinked
+        let edges = [(n[0], n[1]), (n[0], n[2]), (n[1], n[3]), (n[2], n[3])];
+        let topology = Topology::new(n.clone(), edges);
+        assert_eq!(topology.downstream(n[0]), vec![n[1], n[2], n[3]]);
+        assert_eq!(topology.upstream(n[3]), vec![n[1], n[2], n[0]]);
+        assert_eq!(topology.downstream(n[4]), vec![]);
+        assert_eq!(topology.upstream(NodeId { id: 99 }), vec![]);
+    }
+
+    #[test]
+    fn groups_connected_components() {
+        let n = nodes(6);
+        let topology = Topology::new(n.clone(), [(n[3], n[0]), (n[4], n[3]), (n[5], n[1])]);
+        assert_eq!(
+            topology.connected_components(),
+            vec![vec![n[0], n[3], n[4]], vec![n[1], n[5]], vec![n[2]]]
+        );
+    }
+
+    #[test]
+    fn adds_nodes_only_found_in_edges() {
+        let n = nodes(3);
+        let topology = Topology::new([n[0]], [(n[0], n[1]), (n[2], n[0]), (n[0], n[1])]);
+        assert_eq!(topology.nodes(), &n[..]);
+        assert_eq!(topology.successors(n[0]), vec![n[1]]);
+    }
+
+    #[test]
+    fn handles_deep_chains_without_recursion() {
+        let n = nodes(100_000);
+        let edges: Vec<_> = n.windows(2).map(|pair| (pair[0], pair[1])).collect();
+        let topology = Topology::new(n.iter().rev().copied(), &edges);
+
+        assert_eq!(topology.topological_order(), Ok(n.clone()));
+        let components = topology.strongly_connected_components();
+        assert_eq!(components.len(), n.len());
+        assert_eq!(components[0], vec![n[n.len() - 1]]);
+        assert_eq!(topology.downstream(n[0]).len(), n.len() - 1);
+
+        // Closing the chain makes a single cycle of all nodes.
+        let mut edges = edges;
+        edges.push((n[n.len() - 1], n[0]));
+        let topology = Topology::new(n.clone(), &edges);
+        assert_eq!(topology.topological_order().unwrap_err().nodes(), &n[..]);
+    }
+}